@@ -21,4 +21,33 @@ fn main() {
         .unwrap_or_else(|e| panic!("protobuf compile error: {}", e));
 
     println!("cargo:rerun-if-changed={}", proto_file);
+
+    // embed the commit the node is built from, if built from a git checkout
+    if let Some(commit) = std::process::Command::new("git")
+        .args(&["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+    {
+        println!("cargo:rustc-env=FARCASTER_GIT_COMMIT={}", commit.trim());
+    }
+    println!("cargo:rerun-if-changed=.git/HEAD");
+
+    // embed the farcaster_core version resolved in the lock file
+    let lock = std::fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let mut lines = lock.lines();
+    while let Some(line) = lines.next() {
+        if line == "name = \"farcaster_core\"" {
+            if let Some(version) = lines
+                .next()
+                .and_then(|line| line.strip_prefix("version = \""))
+                .and_then(|version| version.strip_suffix('"'))
+            {
+                println!("cargo:rustc-env=FARCASTER_CORE_VERSION={}", version);
+            }
+            break;
+        }
+    }
+    println!("cargo:rerun-if-changed=Cargo.lock");
 }
@@ -49,15 +49,30 @@ fn main() -> Result<(), Error> {
     opts.process();
     trace!("Processed arguments: {:?}", &opts);
 
+    debug!("Config file path: {}", &opts.config);
+    let config = parse_config(&opts.config)?;
+    debug!("Configuration: {:#?}", &config);
+
+    // the high-water marks given on the command line take precedence over the configured ones,
+    // which the launched services inherit through the environment
+    if opts.shared.zmq_send_hwm.is_none() {
+        if let Some(hwm) = config.get_zmq_send_high_water_mark() {
+            std::env::set_var("FARCASTER_ZMQ_SEND_HWM", hwm.to_string());
+            opts.shared.zmq_send_hwm = Some(hwm);
+        }
+    }
+    if opts.shared.zmq_receive_hwm.is_none() {
+        if let Some(hwm) = config.get_zmq_receive_high_water_mark() {
+            std::env::set_var("FARCASTER_ZMQ_RECEIVE_HWM", hwm.to_string());
+            opts.shared.zmq_receive_hwm = Some(hwm);
+        }
+    }
+
     let service_config: ServiceConfig = opts.shared.clone().into();
     trace!("Daemon configuration: {:#?}", &service_config);
     debug!("MSG RPC socket {}", &service_config.msg_endpoint);
     debug!("CTL RPC socket {}", &service_config.ctl_endpoint);
 
-    debug!("Config file path: {}", &opts.config);
-    let config = parse_config(&opts.config)?;
-    debug!("Configuration: {:#?}", &config);
-
     // Generate runtime token
     let mut dest = [0u8; 16];
     thread_rng().fill_bytes(&mut dest);
@@ -248,6 +248,7 @@ fn main() {
         local_socket,
         local_node,
         forked_from_listener,
+        opts.announce_protocol_version,
     )
     .expect("Error running peerd runtime");
 
@@ -20,9 +20,10 @@ extern crate log;
 use clap::Parser;
 
 use farcaster_node::cli::Opts;
-use farcaster_node::rpc::Client;
+use farcaster_node::rpc::request::Token;
+use farcaster_node::rpc::{Client, Request};
 use farcaster_node::LogStyle;
-use farcaster_node::ServiceConfig;
+use farcaster_node::{ServiceConfig, ServiceId};
 use microservices::shell::Exec;
 
 fn main() {
@@ -38,6 +39,12 @@ fn main() {
 
     let mut client = Client::with(service_config).expect("Error initializing client");
 
+    if let Some(token) = opts.tenant_token.clone() {
+        client
+            .request(ServiceId::Farcasterd, Request::Authenticate(Token(token)))
+            .expect("Error authenticating client");
+    }
+
     trace!("Executing command: {:?}", opts.command);
     opts.command
         .exec(&mut client)
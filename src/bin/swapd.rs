@@ -60,6 +60,12 @@ fn main() {
         opts.swap_id,
         opts.public_offer,
         opts.trade_role,
+        opts.arbitrating_syncer_id,
+        opts.accordant_syncer_id,
+        opts.arbitrating_syncer_instance,
+        opts.accordant_syncer_instance,
+        opts.funding_progress_confirmations,
+        opts.multi_tenant,
     )
     .expect("Error running swapd runtime");
 
@@ -12,11 +12,15 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use crate::rpc::request::{Address, AddressSecretKey};
-use crate::syncerd::{SweepAddressAddendum, SweepBitcoinAddress, SweepMoneroAddress};
+use crate::config::FARCASTER_DEFAULT_FEE_STRATEGY;
+use crate::rpc::request::{
+    Address, AddressSecretKey, OptionDetails, Outcome, ProgressEvent, SwapTimeline,
+};
+use crate::syncerd::{SweepAddressAddendum, SweepBitcoinAddress, SweepMoneroAddress, TaskId};
 use farcaster_core::swap::btcxmr::Offer;
 use std::io::{self, Read};
 use std::str::FromStr;
+use std::time::Duration;
 use uuid::Uuid;
 
 use internet2::addr::{InetSocketAddr, NodeAddr};
@@ -25,12 +29,20 @@ use microservices::shell::Exec;
 use clap::IntoApp;
 use clap_complete::generate;
 use clap_complete::shells::*;
-use farcaster_core::{blockchain::Network, negotiation::PublicOffer, role::SwapRole, swap::SwapId};
+use farcaster_core::{
+    blockchain::{FeeStrategy, Network},
+    negotiation::PublicOffer,
+    role::SwapRole,
+    swap::SwapId,
+};
 
 use super::Command;
 use crate::rpc::{request, Client, Request};
 use crate::{Error, LogStyle, ServiceId};
 
+/// Exit status of the wait command when the swap did not reach its outcome in time
+const WAIT_TIMEOUT_EXIT_CODE: i32 = 5;
+
 impl Exec for Command {
     type Client = Client;
     type Error = Error;
@@ -69,8 +81,13 @@ impl Exec for Command {
                 }
             }
 
-            Command::Peers => {
-                runtime.request(ServiceId::Farcasterd, Request::ListPeers)?;
+            Command::Peers { protocol_versions } => {
+                let request = if protocol_versions {
+                    Request::ListPeerProtocolVersions
+                } else {
+                    Request::ListPeers
+                };
+                runtime.request(ServiceId::Farcasterd, request)?;
                 runtime.report_response_or_fail()?;
             }
 
@@ -79,9 +96,76 @@ impl Exec for Command {
                 runtime.report_response_or_fail()?;
             }
 
+            Command::SyncerClients => {
+                runtime.request(ServiceId::Farcasterd, Request::SyncerClients)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::SubscriptionTopology => {
+                runtime.request(ServiceId::Farcasterd, Request::SubscriptionTopology)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::Services => {
+                runtime.request(ServiceId::Farcasterd, Request::ServiceInventory)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::StateMachines => {
+                runtime.request(ServiceId::Farcasterd, Request::ListStateMachines)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::ValueLocked => {
+                runtime.request(ServiceId::Farcasterd, Request::TotalValueLocked)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::FundingCancellations => {
+                runtime.request(ServiceId::Farcasterd, Request::FundingCancellationBreakdown)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::ValidateConfig => {
+                runtime.request(ServiceId::Farcasterd, Request::ValidateConfig)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::ActiveNetworks => {
+                runtime.request(ServiceId::Farcasterd, Request::ActiveNetworks)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::SwapStates => {
+                runtime.request(ServiceId::Farcasterd, Request::SwapStateHistogram)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::Paths => {
+                runtime.request(ServiceId::Farcasterd, Request::GetPaths)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::SwapsInfo { swap_ids } => {
+                runtime.request(ServiceId::Farcasterd, Request::SwapInfoBatch(swap_ids))?;
+                runtime.report_response_or_fail()?;
+            }
+
             // TODO: only list offers matching list of OfferIds
-            Command::ListOffers { select } => {
-                runtime.request(ServiceId::Farcasterd, Request::ListOffers(select.into()))?;
+            Command::ListOffers { select, direction } => {
+                let request = match direction {
+                    Some(direction) => Request::ListOffersByDirection(request::ListOffers {
+                        selector: select.into(),
+                        direction: Some(direction),
+                    }),
+                    None => Request::ListOffers(select.into()),
+                };
+                runtime.request(ServiceId::Farcasterd, request)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::OfferBook => {
+                runtime.request(ServiceId::Farcasterd, Request::OfferBook)?;
                 runtime.report_response_or_fail()?;
             }
 
@@ -90,11 +174,36 @@ impl Exec for Command {
                 runtime.report_response_or_fail()?;
             }
 
+            Command::TestListenReachability => {
+                runtime.request(ServiceId::Farcasterd, Request::TestListenReachability)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::ReloadSyncerServers { network } => {
+                runtime.request(ServiceId::Farcasterd, Request::ReloadSyncerServers(network))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::SyncerServers { network } => {
+                runtime.request(ServiceId::Farcasterd, Request::GetSyncerServers(network))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::ListenOffers { listen } => {
+                runtime.request(ServiceId::Farcasterd, Request::ListenOffers(listen))?;
+                runtime.report_response_or_fail()?;
+            }
+
             Command::ListCheckpoints => {
                 runtime.request(ServiceId::Database, Request::RetrieveAllCheckpointInfo)?;
                 runtime.report_response_or_fail()?;
             }
 
+            Command::ListRestorable => {
+                runtime.request(ServiceId::Farcasterd, Request::ListRestorable)?;
+                runtime.report_response_or_fail()?;
+            }
+
             Command::RestoreCheckpoint { swap_id } => {
                 runtime.request(ServiceId::Farcasterd, Request::RestoreCheckpoint(swap_id))?;
                 runtime.report_response_or_fail()?;
@@ -115,6 +224,11 @@ impl Exec for Command {
                 public_ip_addr,
                 bind_ip_addr,
                 port,
+                electrum_server,
+                monero_daemon,
+                monero_rpc_wallet,
+                syncer_instance,
+                expiry,
             } => {
                 // Monero local address types are mainnet address types
                 if network != accordant_addr.network.into() && network != Network::Local {
@@ -156,6 +270,12 @@ impl Exec for Command {
                     );
                     return Ok(());
                 }
+                let explicit_fee_strategy = fee_strategy.is_some();
+                // farcasterd overrides the fallback with its configured default if any
+                let fee_strategy = fee_strategy.unwrap_or_else(|| {
+                    FeeStrategy::from_str(FARCASTER_DEFAULT_FEE_STRATEGY)
+                        .expect("valid default fee strategy")
+                });
                 let offer = Offer {
                     uuid: Uuid::new_v4(),
                     network,
@@ -176,6 +296,14 @@ impl Exec for Command {
                     bind_addr,
                     arbitrating_addr,
                     accordant_addr,
+                    explicit_fee_strategy,
+                    syncer_servers: request::SyncerServersOverride {
+                        electrum_server,
+                        monero_daemon,
+                        monero_rpc_wallet,
+                        syncer_instance,
+                    },
+                    expiry: expiry.map(Duration::from_secs),
                 };
                 runtime.request(ServiceId::Farcasterd, Request::MakeOffer(proto_offer))?;
                 // report success or failure of the request to cli
@@ -198,6 +326,10 @@ impl Exec for Command {
                 bitcoin_address,
                 monero_address,
                 without_validation,
+                electrum_server,
+                monero_daemon,
+                monero_rpc_wallet,
+                syncer_instance,
             } => {
                 let PublicOffer {
                     version: _,
@@ -267,15 +399,34 @@ impl Exec for Command {
                     // pass offer to farcasterd to initiate the swap
                     runtime.request(
                         ServiceId::Farcasterd,
-                        Request::TakeOffer((public_offer, bitcoin_address, monero_address).into()),
+                        Request::TakeOffer(request::PubOffer {
+                            public_offer,
+                            external_address: bitcoin_address,
+                            internal_address: monero_address,
+                            syncer_servers: request::SyncerServersOverride {
+                                electrum_server,
+                                monero_daemon,
+                                monero_rpc_wallet,
+                                syncer_instance,
+                            },
+                        }),
                     )?;
                     // report success of failure of the request to cli
                     runtime.report_response_or_fail()?;
                 }
             }
 
-            Command::RevokeOffer { public_offer } => {
-                runtime.request(ServiceId::Farcasterd, Request::RevokeOffer(public_offer))?;
+            Command::RevokeOffer {
+                public_offer,
+                dry_run,
+            } => {
+                runtime.request(
+                    ServiceId::Farcasterd,
+                    Request::RevokeOffer(request::RevokeOffer {
+                        public_offer,
+                        dry_run,
+                    }),
+                )?;
                 runtime.report_response_or_fail()?;
             }
 
@@ -284,11 +435,204 @@ impl Exec for Command {
                 runtime.report_response_or_fail()?;
             }
 
-            Command::Progress { swapid, follow } => {
+            Command::SetConfirmationTarget {
+                swap_id,
+                blockchain,
+                confs,
+            } => {
+                runtime.request(
+                    ServiceId::Swap(swap_id),
+                    Request::SetConfirmationTarget(request::ConfirmationTarget {
+                        swap_id,
+                        blockchain,
+                        confs,
+                    }),
+                )?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::ReissueTasks { swap_id } => {
+                runtime.request(ServiceId::Swap(swap_id), Request::ReissueSwapTasks(swap_id))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::Timelocks { swap_id } => {
+                runtime.request(ServiceId::Swap(swap_id), Request::SwapTimelocks(swap_id))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::KillSwap { swap_id } => {
+                runtime.request(ServiceId::Farcasterd, Request::KillSwap(swap_id))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::Drain { stop } => {
+                runtime.request(ServiceId::Farcasterd, Request::Drain(!stop))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::AutoFunding { disable } => {
+                runtime.request(ServiceId::Farcasterd, Request::SetAutoFunding(!disable))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::Nickname { swap_id, nickname } => {
+                runtime.request(
+                    ServiceId::Farcasterd,
+                    Request::SetSwapNickname(swap_id, nickname),
+                )?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::PauseSyncer {
+                blockchain,
+                network,
+                resume,
+            } => {
+                let request = if resume {
+                    Request::ResumeSyncer(blockchain, network)
+                } else {
+                    Request::PauseSyncer(blockchain, network)
+                };
+                runtime.request(ServiceId::Farcasterd, request)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::RefreshConnections => {
+                runtime.request(ServiceId::Farcasterd, Request::RefreshConnections)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::CancelPendingRestores => {
+                runtime.request(ServiceId::Farcasterd, Request::CancelPendingRestores)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::Version => {
+                runtime.request(ServiceId::Farcasterd, Request::Version)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::TestLaunch { service } => {
+                runtime.request(ServiceId::Farcasterd, Request::TestLaunch(service))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::StartupInfo => {
+                runtime.request(ServiceId::Farcasterd, Request::StartupInfo)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::StateDwell { swap_id } => {
+                runtime.request(ServiceId::Farcasterd, Request::SwapStateDwell(swap_id))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::Timeline { swap_id } => {
+                runtime.request(ServiceId::Farcasterd, Request::SwapTimeline(swap_id))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::Resync { swap_id } => {
+                runtime.request(ServiceId::Farcasterd, Request::ResyncSwap(swap_id))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::RebindConnection { swap_id, new_peer } => {
+                runtime.request(
+                    ServiceId::Farcasterd,
+                    Request::RebindSwapConnection(request::RebindSwapConnection {
+                        swap_id,
+                        new_peer,
+                    }),
+                )?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::Throughput => {
+                runtime.request(ServiceId::Farcasterd, Request::ThroughputStats)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::UnprocessedRequests { clear } => {
+                runtime.request(
+                    ServiceId::Farcasterd,
+                    Request::GetUnprocessedRequests(request::GetUnprocessedRequests { clear }),
+                )?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::VerifyNodeKeys => {
+                runtime.request(ServiceId::Farcasterd, Request::VerifyNodeKeys)?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::HasSeenOffer { offer_id } => {
+                runtime.request(
+                    ServiceId::Farcasterd,
+                    Request::HasSeenOffer(request::HasSeenOffer { offer_id }),
+                )?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::Counterparty { swap_id } => {
+                runtime.request(ServiceId::Farcasterd, Request::SwapCounterparty(swap_id))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::PeerReputation { node_id } => {
+                runtime.request(ServiceId::Farcasterd, Request::GetPeerReputation(node_id))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::OffersByMaker { node_id } => {
+                runtime.request(ServiceId::Farcasterd, Request::OffersByMaker(node_id))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::TeardownPreviewPeer { node_addr } => {
+                runtime.request(
+                    ServiceId::Farcasterd,
+                    Request::TeardownPreview(ServiceId::Peer(node_addr)),
+                )?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::TeardownPreviewSyncer {
+                blockchain,
+                network,
+            } => {
+                runtime.request(
+                    ServiceId::Farcasterd,
+                    Request::TeardownPreview(ServiceId::Syncer(blockchain, network)),
+                )?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::StateDump { swap_id } => {
+                runtime.request(ServiceId::Farcasterd, Request::SwapStateDump(swap_id))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::SwapTransactions { swap_id } => {
+                runtime.request(ServiceId::Farcasterd, Request::SwapTransactions(swap_id))?;
+                runtime.report_response_or_fail()?;
+            }
+
+            Command::Progress {
+                swapid,
+                follow,
+                batched,
+            } => {
                 if follow {
                     // subscribe to progress event and loop until Finish event is received or user
                     // ctrl-c the cli. Expect to recieve a stream of event responses
-                    runtime.request(ServiceId::Farcasterd, Request::SubscribeProgress(swapid))?;
+                    let subscribe = if batched {
+                        Request::SubscribeProgressBatched(swapid)
+                    } else {
+                        Request::SubscribeProgress(swapid)
+                    };
+                    runtime.request(ServiceId::Farcasterd, subscribe)?;
                     let res = runtime.report_progress();
                     // if user didn't ctrl-c before that point we can cleanly unsubscribe the
                     // client from the notification stream and then return the result from report
@@ -303,11 +647,66 @@ impl Exec for Command {
                 }
             }
 
+            Command::ExportProgress { swapid } => {
+                runtime.request(ServiceId::Farcasterd, Request::ReadProgress(swapid))?;
+                let progress = if let Request::SwapProgress(progress) = runtime.report_failure()? {
+                    progress
+                } else {
+                    return Err(Error::Farcaster("Received unexpected response".to_string()));
+                };
+                // the outcome is read from the timeline of the swap, pending if it is unknown
+                runtime.request(ServiceId::Farcasterd, Request::SwapTimeline(swapid))?;
+                let outcome = match runtime.response()? {
+                    Request::SwapTimelineReport(SwapTimeline { outcome, .. }) => outcome,
+                    _ => None,
+                };
+                print!("{}", progress_to_csv(&progress, outcome));
+            }
+
+            Command::Wait { swap_id, timeout } => {
+                if let Some(timeout) = timeout {
+                    // the client blocks on the subscription stream, give up from another thread
+                    std::thread::spawn(move || {
+                        std::thread::sleep(Duration::from_secs(timeout));
+                        eprintln!("{} timed out waiting for swap {}", "error:".err(), swap_id);
+                        std::process::exit(WAIT_TIMEOUT_EXIT_CODE);
+                    });
+                }
+                runtime.request(ServiceId::Farcasterd, Request::SubscribeNodeEvents)?;
+                runtime.request(ServiceId::Farcasterd, Request::SubscribeProgress(swap_id))?;
+                // the swap may have ended before subscribing, its timeline then has the outcome
+                runtime.request(ServiceId::Farcasterd, Request::SwapTimeline(swap_id))?;
+                let res = runtime.wait_outcome(swap_id);
+                runtime.request(ServiceId::Farcasterd, Request::UnsubscribeProgress(swap_id))?;
+                runtime.request(ServiceId::Farcasterd, Request::UnsubscribeNodeEvents)?;
+                let code = match res {
+                    Ok(outcome) => {
+                        println!("{}", outcome);
+                        match outcome {
+                            Outcome::Buy => 0,
+                            Outcome::Refund => 2,
+                            Outcome::Punish => 3,
+                            Outcome::Abort => 4,
+                        }
+                    }
+                    Err(err) => {
+                        eprintln!("{} {}", "error:".err(), err.err());
+                        1
+                    }
+                };
+                std::process::exit(code);
+            }
+
             Command::NeedsFunding { blockchain } => {
                 runtime.request(ServiceId::Farcasterd, Request::NeedsFunding(blockchain))?;
                 runtime.report_response_or_fail()?;
             }
 
+            Command::AllFundingNeeds => {
+                runtime.request(ServiceId::Farcasterd, Request::AllFundingNeeds)?;
+                runtime.report_response_or_fail()?;
+            }
+
             Command::SweepBitcoinAddress {
                 source_address,
                 destination_address,
@@ -359,6 +758,14 @@ impl Exec for Command {
                 }
             }
 
+            Command::SweepStatus { task_id } => {
+                runtime.request(
+                    ServiceId::Farcasterd,
+                    Request::QuerySweepStatus(TaskId(task_id)),
+                )?;
+                runtime.report_response_or_fail()?;
+            }
+
             Command::Completion { shell } => {
                 let mut app = super::Opts::command();
                 let name = app.get_name().to_string();
@@ -411,3 +818,44 @@ fn offer_buy_information(offer: &Offer) -> String {
         ),
     }
 }
+
+/// Formats the progress of a swap as a CSV timeline, one row per event and a last row with the
+/// outcome of the swap, or pending if it did not reach one yet. The timestamp is the unix time
+/// at which farcasterd queued the event, left empty if it is unknown.
+fn progress_to_csv(progress: &request::SwapProgress, outcome: Option<Outcome>) -> String {
+    let mut csv = String::from("timestamp,event-type,detail\n");
+    for (i, event) in progress.progress.iter().enumerate() {
+        let (event_type, detail) = match event {
+            ProgressEvent::Message(message) => ("message", message.clone()),
+            ProgressEvent::StateTransition(transition) => ("transition", transition.clone()),
+            ProgressEvent::Success(OptionDetails(details)) => {
+                ("success", details.clone().unwrap_or_default())
+            }
+            ProgressEvent::Failure(failure) => ("failure", failure.info.clone()),
+        };
+        let timestamp = progress
+            .timestamps
+            .get(i)
+            .map_or_else(String::new, |timestamp| timestamp.to_string());
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            timestamp,
+            event_type,
+            csv_field(&detail)
+        ));
+    }
+    csv.push_str(&format!(
+        ",outcome,{}\n",
+        outcome.map_or_else(|| "pending".to_string(), |outcome| outcome.to_string())
+    ));
+    csv
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break
+fn csv_field(field: &str) -> String {
+    if field.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -14,9 +14,13 @@
 
 use bitcoin::Address as BtcAddress;
 use clap_complete::shells::Shell;
+use internet2::addr::{InetSocketAddr, NodeAddr};
 use monero::Address as XmrAddress;
 use std::net::IpAddr;
 use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::rpc::request::OfferDirection;
 
 use farcaster_core::{
     bitcoin::{fee::SatPerVByte, timelock::CSVTimelock},
@@ -34,6 +38,10 @@ pub struct Opts {
     #[clap(flatten)]
     pub shared: crate::opts::Opts,
 
+    /// Tenant token to authenticate with when the node is shared by multiple tenants
+    #[clap(long, env = "FARCASTER_TENANT_TOKEN")]
+    pub tenant_token: Option<String>,
+
     /// Command to execute
     #[clap(subcommand)]
     pub command: Command,
@@ -57,12 +65,29 @@ pub enum Command {
     },
 
     /// Lists existing peer connections
-    Peers,
+    #[display("peers<{protocol_versions}>")]
+    Peers {
+        /// Also list the protocol version negotiated over each connection
+        #[clap(long)]
+        protocol_versions: bool,
+    },
 
     /// Lists running swaps
     #[clap(aliases = &["ls"])]
     ListSwaps,
 
+    /// Gives information on several swaps at once, on all the swaps with a running swapd if no
+    /// swap is given
+    #[display("swaps-info<{swap_ids:?}>")]
+    SwapsInfo {
+        /// The swaps to give information on
+        swap_ids: Vec<SwapId>,
+    },
+
+    /// Shows the open offers with their amounts, direction, maker and time to live, in a form
+    /// suited to publish them
+    OfferBook,
+
     /// Lists public offers created by daemon
     #[clap(aliases = &["lo"])]
     ListOffers {
@@ -73,6 +98,10 @@ pub enum Command {
             possible_values = &["open", "Open", "inprogress", "in_progress", "ended", "Ended", "all", "All"],
         )]
         select: OfferSelector,
+
+        /// Only list the offers letting the taker buy or sell bitcoin: buy-btc or sell-btc
+        #[clap(long)]
+        direction: Option<OfferDirection>,
     },
 
     /// Gives information on an open offer
@@ -87,10 +116,83 @@ pub enum Command {
     #[clap(aliases = &["ll"])]
     ListListens,
 
+    /// Lists the running syncers and the number of swaps each of them serves
+    SyncerClients,
+
+    /// Lists the swaps with progress subscriptions and the clients subscribed to each of them
+    SubscriptionTopology,
+
+    /// Lists the services farcasterd registered and the ones it launched that did not connect
+    /// yet
+    Services,
+
+    /// Lists the trade and syncer state machines run by farcasterd, e.g. to attach them to a bug
+    /// report
+    StateMachines,
+
+    /// Sums the funds locked by the running swaps of this node, per blockchain
+    ValueLocked,
+
+    /// Counts the swaps whose funding was canceled since farcasterd started, per blockchain and
+    /// reason
+    FundingCancellations,
+
+    /// Checks the configuration of the running node and the connection of its core services,
+    /// listing the problems and warnings found
+    ValidateConfig,
+
+    /// Lists the networks the node is swapping on, across its running swaps and open offers
+    ActiveNetworks,
+
+    /// Counts the swaps in each trade state machine state, e.g. to spot swaps piling up
+    SwapStates,
+
+    /// Shows the data directory and bus sockets the node launches its services with, and the
+    /// grpc endpoint if enabled
+    Paths,
+
+    /// Test whether the addresses takers use to reach the listeners accept a connection from
+    /// the node itself, through the Tor proxy if one is set
+    TestListenReachability,
+
+    /// Lists the open offers advertised by a listener
+    #[display("listen-offers<{listen}>")]
+    ListenOffers {
+        /// The listener address as returned by list-listens
+        listen: InetSocketAddr,
+    },
+
+    /// Reloads the syncer servers of a network from the config file. The idle syncers are
+    /// terminated, the ones serving swaps once they become idle, and relaunched with the new
+    /// servers on their next use
+    #[display("reload-syncer-servers<{network}>")]
+    ReloadSyncerServers {
+        /// The network to reload the syncer servers of
+        #[clap(
+            possible_values = &["Testnet", "testnet", "Mainnet", "mainnet", "Local", "local"]
+        )]
+        network: Network,
+    },
+
+    /// Shows the syncer servers the node uses for a network, credentials are redacted
+    #[display("syncer-servers<{network}>")]
+    SyncerServers {
+        /// The network to show the syncer servers of
+        #[clap(
+            default_value = "testnet",
+            possible_values = &["Testnet", "testnet", "Mainnet", "mainnet", "Local", "local"]
+        )]
+        network: Network,
+    },
+
     /// Lists saved checkpoints of the swaps
     #[clap(aliases = &["lc"])]
     ListCheckpoints,
 
+    /// Lists saved checkpoints of the swaps that are not running, i.e. that can be restored
+    #[clap(aliases = &["lr"])]
+    ListRestorable,
+
     /// Restores saved checkpoint of a swap
     #[clap(aliases = &["r"])]
     RestoreCheckpoint {
@@ -163,9 +265,10 @@ pub enum Command {
         #[clap(long, default_value = "5")]
         punish_timelock: CSVTimelock,
 
-        /// The chosen fee strategy for the arbitrating transactions.
-        #[clap(long, default_value = "1 satoshi/vByte")]
-        fee_strategy: FeeStrategy<SatPerVByte>,
+        /// The chosen fee strategy for the arbitrating transactions. If omitted, the default
+        /// fee strategy configured in farcasterd for the network is used, or 1 satoshi/vByte.
+        #[clap(long)]
+        fee_strategy: Option<FeeStrategy<SatPerVByte>>,
 
         /// Public IPv4 or IPv6 address present in the public offer allowing taker to connect.
         #[clap(short = 'I', long, default_value = "127.0.0.1")]
@@ -178,6 +281,27 @@ pub enum Command {
         /// Port to use; defaults to the native LN port.
         #[clap(short, long, default_value = "9735")]
         port: u16,
+
+        /// Electrum server used by the swap instead of the configured one.
+        #[clap(long)]
+        electrum_server: Option<String>,
+
+        /// Monero daemon used by the swap instead of the configured one.
+        #[clap(long)]
+        monero_daemon: Option<String>,
+
+        /// Monero rpc wallet used by the swap instead of the configured one.
+        #[clap(long)]
+        monero_rpc_wallet: Option<String>,
+
+        /// Named syncer instance of the configuration serving the swap, exclusive with the
+        /// servers above.
+        #[clap(long)]
+        syncer_instance: Option<String>,
+
+        /// Number of seconds after which the offer is revoked if no taker committed to it.
+        #[clap(long)]
+        expiry: Option<u64>,
     },
 
     /// Taker accepts offer and connects to maker's daemon to start the trade.
@@ -197,6 +321,23 @@ pub enum Command {
         /// Accept the public offer without validation.
         #[clap(short, long)]
         without_validation: bool,
+
+        /// Electrum server used by the swap instead of the configured one.
+        #[clap(long)]
+        electrum_server: Option<String>,
+
+        /// Monero daemon used by the swap instead of the configured one.
+        #[clap(long)]
+        monero_daemon: Option<String>,
+
+        /// Monero rpc wallet used by the swap instead of the configured one.
+        #[clap(long)]
+        monero_rpc_wallet: Option<String>,
+
+        /// Named syncer instance of the configuration serving the swap, exclusive with the
+        /// servers above.
+        #[clap(long)]
+        syncer_instance: Option<String>,
     },
 
     /// Revoke offer accepts an offer and revokes it within the runtime.
@@ -204,6 +345,10 @@ pub enum Command {
     RevokeOffer {
         /// The offer to be canceled.
         public_offer: PublicOffer,
+
+        /// Only report what revoking the offer would affect, without revoking it.
+        #[clap(long)]
+        dry_run: bool,
     },
 
     /// Abort a swap if it has not locked yet.
@@ -213,6 +358,232 @@ pub enum Command {
         swap_id: SwapId,
     },
 
+    /// Change the number of confirmations a running swap waits for before considering its
+    /// transactions on a blockchain final. Only possible while the swap's lock on that blockchain
+    /// is not yet final.
+    #[display("set-confirmation-target<{swap_id} {blockchain} {confs}>")]
+    SetConfirmationTarget {
+        /// The swap to update
+        swap_id: SwapId,
+        /// The blockchain the confirmation target applies to
+        blockchain: Blockchain,
+        /// The number of confirmations required
+        confs: u32,
+    },
+
+    /// Send the syncer tasks of a running swap again, e.g. after its syncer restarted and lost
+    /// them, without restarting the swap.
+    #[display("reissue-tasks<{swap_id}>")]
+    ReissueTasks {
+        /// The swap to reissue the tasks of
+        swap_id: SwapId,
+    },
+
+    /// Shows the cancel and punish timelocks of a running swap, and the Bitcoin heights from
+    /// which they are valid once its lock transaction is mined
+    #[display("timelocks<{swap_id}>")]
+    Timelocks {
+        /// The swap to show the timelocks of
+        swap_id: SwapId,
+    },
+
+    /// Forcibly terminate a swap daemon, e.g. if it hangs. Last resort tool: the swap is dropped
+    /// without refunding or punishing, its checkpoint is kept so it can be restored.
+    #[display("kill-swap<{swap_id}>")]
+    KillSwap {
+        /// The swap to be killed
+        swap_id: SwapId,
+    },
+
+    /// Stop accepting new offers and swaps, e.g. before a maintenance, running swaps complete
+    /// as usual.
+    #[display("drain<{stop}>")]
+    Drain {
+        /// Leave drain mode and accept new offers and swaps again
+        #[clap(long)]
+        stop: bool,
+    },
+
+    /// Enable automatic funding of swaps, overriding the configuration until farcasterd
+    /// restarts. Swaps are only funded on networks with configured auto-funding servers.
+    #[display("auto-funding<{disable}>")]
+    AutoFunding {
+        /// Disable automatic funding instead
+        #[clap(long)]
+        disable: bool,
+    },
+
+    /// Sets a nickname shown next to the state of a swap in the logs, an empty nickname clears it
+    #[display("nickname<{swap_id}, {nickname}>")]
+    Nickname {
+        /// The swap to nickname
+        swap_id: SwapId,
+        /// The nickname, at most 32 characters
+        nickname: String,
+    },
+
+    /// Pauses the syncers of a blockchain for maintenance, e.g. before restarting its backend.
+    /// Their tasks are queued and the swaps relying on them keep running.
+    #[display("pause-syncer<{blockchain} {network} {resume}>")]
+    PauseSyncer {
+        /// The blockchain of the syncers
+        blockchain: Blockchain,
+        /// The network of the syncers
+        #[clap(
+            default_value = "testnet",
+            possible_values = &["Testnet", "testnet", "Mainnet", "mainnet", "Local", "local"]
+        )]
+        network: Network,
+        /// Resume the syncers instead
+        #[clap(long)]
+        resume: bool,
+    },
+
+    /// Previews which swaps terminating the connection to a peer would affect, and if they would
+    /// survive it
+    #[display("teardown-preview-peer<{node_addr}>")]
+    TeardownPreviewPeer {
+        /// The connected peer, e.g. as listed by list-peers
+        node_addr: NodeAddr,
+    },
+
+    /// Previews which swaps terminating the default syncer of a blockchain would affect, and if
+    /// they would survive it
+    #[display("teardown-preview-syncer<{blockchain} {network}>")]
+    TeardownPreviewSyncer {
+        /// The blockchain of the syncer
+        blockchain: Blockchain,
+        /// The network of the syncer
+        #[clap(
+            default_value = "testnet",
+            possible_values = &["Testnet", "testnet", "Mainnet", "mainnet", "Local", "local"]
+        )]
+        network: Network,
+    },
+
+    /// Checks that every connected peer daemon still answers, and removes the connections of the
+    /// ones that do not
+    #[display("refresh-connections")]
+    RefreshConnections,
+
+    /// Cancels the restores whose swapd did not receive the checkpoint yet, and lists the ones
+    /// that cannot be cancelled anymore
+    #[display("cancel-pending-restores")]
+    CancelPendingRestores,
+
+    /// Shows the version of the running node, the commit it was built from and the
+    /// farcaster_core version it links against
+    #[display("version")]
+    Version,
+
+    /// Runs a service binary with `--version` without integrating it into the node, to check
+    /// that it is found and runnable
+    #[display("test-launch<{service}>")]
+    TestLaunch {
+        /// The service binary to run: swapd, peerd, syncerd, walletd, databased or grpcd
+        service: String,
+    },
+
+    /// Shows the parameters the node was launched with: version, data directory, sockets, tor
+    /// proxy, grpc and auto-funding, and the networks with configured syncers
+    #[display("startup-info")]
+    StartupInfo,
+
+    /// Shows the current state of a running swap and for how long it has been in it, e.g. to spot
+    /// a stalled swap
+    #[display("state-dwell<{swap_id}>")]
+    StateDwell {
+        /// The swap to show the state of
+        swap_id: SwapId,
+    },
+
+    /// Shows when a swap was initiated, funded, locked and ended, the most recently ended swaps
+    /// are kept
+    #[display("timeline<{swap_id}>")]
+    Timeline {
+        /// The swap to show the timeline of
+        swap_id: SwapId,
+    },
+
+    /// Reloads the checkpoint of a running swap and reconciles the state farcasterd keeps about
+    /// it, the checkpoint wins any discrepancy
+    #[display("resync<{swap_id}>")]
+    Resync {
+        /// The swap to resync
+        swap_id: SwapId,
+    },
+
+    /// Binds a running swap to another connection with its counterparty, e.g. if it still uses
+    /// a dead connection after the counterparty reconnected
+    #[display("rebind-connection<{swap_id} {new_peer}>")]
+    RebindConnection {
+        /// The swap to rebind
+        swap_id: SwapId,
+        /// The connected peer to bind the swap to, e.g. as listed by list-peers
+        new_peer: NodeAddr,
+    },
+
+    /// Shows the traffic of the node since startup: bytes exchanged with peers by ended swaps and
+    /// requests processed on each bus
+    #[display("throughput")]
+    Throughput,
+
+    /// Lists the most recent requests farcasterd received but did not process
+    #[display("unprocessed-requests")]
+    UnprocessedRequests {
+        /// Forget the listed requests
+        #[clap(long)]
+        clear: bool,
+    },
+
+    /// Checks the node public key is the one derived from the node secret key
+    #[display("verify-node-keys")]
+    VerifyNodeKeys,
+
+    /// Tells if the node ever knew an offer, open, consumed or ended
+    #[display("has-seen-offer<{offer_id}>")]
+    HasSeenOffer {
+        /// The id of the offer
+        offer_id: Uuid,
+    },
+
+    /// Shows the counterparty node of a swap, with the outcomes of the swaps run with it
+    #[display("counterparty<{swap_id}>")]
+    Counterparty {
+        /// The swap to show the counterparty of
+        swap_id: SwapId,
+    },
+
+    /// Shows the outcomes of the swaps run with a counterparty node
+    #[display("peer-reputation<{node_id}>")]
+    PeerReputation {
+        /// The node id of the counterparty
+        node_id: bitcoin::secp256k1::PublicKey,
+    },
+
+    /// Lists the offers of a maker node known to this node, open, in progress or ended
+    #[display("offers-by-maker<{node_id}>")]
+    OffersByMaker {
+        /// The node id of the maker
+        node_id: bitcoin::secp256k1::PublicKey,
+    },
+
+    /// Dump the coordination state farcasterd keeps about a swap, e.g. to attach it to a bug
+    /// report. No key material is part of the dump.
+    #[display("state-dump<{swap_id}>")]
+    StateDump {
+        /// The swap to dump the state of
+        swap_id: SwapId,
+    },
+
+    /// List the ids of the transactions a running swap broadcast or observed so far, e.g. to look
+    /// them up on a block explorer.
+    #[display("swap-transactions<{swap_id}>")]
+    SwapTransactions {
+        /// The swap to list the transactions of
+        swap_id: SwapId,
+    },
+
     /// Request swap progress report.
     #[display("progress<{swapid}>")]
     Progress {
@@ -222,6 +593,32 @@ pub enum Command {
         /// Subscribe to progress and only return when progress is finished.
         #[clap(short, long)]
         follow: bool,
+
+        /// With follow, receive the progress events coalesced in batches instead of one by one,
+        /// e.g. for a slow client following a fast swap.
+        #[clap(long, requires = "follow")]
+        batched: bool,
+    },
+
+    /// Export the progress of a swap as a CSV timeline with the columns timestamp, event-type
+    /// and detail, the last row holds the outcome of the swap.
+    #[display("export-progress<{swapid}>")]
+    ExportProgress {
+        /// The swap id requested.
+        swapid: SwapId,
+    },
+
+    /// Wait for a swap to reach its outcome, following its progress. Exits with status 0 if the
+    /// swap succeeded, 2 if refunded, 3 if punished, 4 if aborted, 5 on timeout and 1 if the
+    /// progress of the swap cannot be followed.
+    #[display("wait<{swap_id}>")]
+    Wait {
+        /// The swap id to wait for.
+        swap_id: SwapId,
+
+        /// Give up waiting after this number of seconds.
+        #[clap(short, long)]
+        timeout: Option<u64>,
     },
 
     /// Returns addresses and amounts that require funding for blockchain.
@@ -231,6 +628,9 @@ pub enum Command {
         blockchain: Blockchain,
     },
 
+    /// Returns addresses and amounts that require funding on all blockchains.
+    AllFundingNeeds,
+
     /// Attempts to sweep any funds on a given bitcoin funding address
     #[display("sweep-bitcoin-address<{source_address} {destination_address}>")]
     SweepBitcoinAddress {
@@ -249,6 +649,14 @@ pub enum Command {
         destination_address: XmrAddress,
     },
 
+    /// Asks the syncer whether a sweep completed and completes it if so, useful when the sweep
+    /// was reported neither successful nor failed
+    #[display("sweep-status<{task_id}>")]
+    SweepStatus {
+        /// The syncer task id of the sweep, as listed by the state machines
+        task_id: u32,
+    },
+
     /// Output shell completion code for the specified shell (bash, zsh or fish)
     ///
     /// The shell code must be evaluated to provide interactive completion of swap-cli commands.
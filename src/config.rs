@@ -12,11 +12,17 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+use crate::rpc::request::ConfigValidation;
 use crate::Error;
-use farcaster_core::blockchain::Network;
+use bitcoin::Txid;
+use farcaster_core::bitcoin::fee::SatPerVByte;
+use farcaster_core::blockchain::{FeeStrategy, Network};
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +34,24 @@ pub const FARCASTER_TESTNET_ELECTRUM_SERVER: &str = "ssl://blockstream.info:993"
 pub const FARCASTER_TESTNET_MONERO_DAEMON: &str = "http://stagenet.community.rino.io:38081";
 pub const FARCASTER_TESTNET_MONERO_RPC_WALLET: &str = "http://localhost:38083";
 
+pub const FARCASTER_DEFAULT_FEE_STRATEGY: &str = "1 satoshi/vByte";
+
+pub const FARCASTER_DEFAULT_MAX_RECONNECT_ATTEMPTS: u32 = 10;
+
+pub const FARCASTER_DEFAULT_MAX_PROGRESS_SUBSCRIBERS: u32 = 100;
+
+pub const FARCASTER_DEFAULT_STARTUP_TIMEOUT: u64 = 30;
+
+pub const FARCASTER_DEFAULT_CLOCK_SKEW_THRESHOLD: u64 = 7200;
+
+pub const FARCASTER_DEFAULT_MAX_SWAPD_RESTARTS: u32 = 3;
+
+/// Placeholder replaced by the txid in the block explorer url templates
+pub const EXPLORER_TXID_PLACEHOLDER: &str = "{txid}";
+
+pub const FARCASTER_MAINNET_BITCOIN_EXPLORER: &str = "https://mempool.space/tx/{txid}";
+pub const FARCASTER_TESTNET_BITCOIN_EXPLORER: &str = "https://mempool.space/testnet/tx/{txid}";
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(crate = "serde_crate")]
 pub struct Config {
@@ -43,7 +67,7 @@ impl Config {
         match &self.farcasterd {
             Some(FarcasterdConfig {
                 auto_funding: Some(AutoFundingConfig { auto_fund, .. }),
-                grpc: _,
+                ..
             }) => *auto_fund,
             _ => false,
         }
@@ -53,27 +77,43 @@ impl Config {
     pub fn is_grpc_enable(&self) -> bool {
         match &self.farcasterd {
             Some(FarcasterdConfig {
-                auto_funding: _,
                 grpc: Some(GrpcConfig { use_grpc, .. }),
+                ..
             }) => *use_grpc,
             _ => false,
         }
     }
 
-    /// Returns the auto-funding configuration for a given network if enable, if None no
-    /// configuration is found
-    pub fn get_auto_funding_config(&self, network: Network) -> Option<AutoFundingServers> {
+    /// Returns the port the grpc server listens on if it is enabled
+    pub fn get_grpc_port(&self) -> Option<u64> {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                grpc:
+                    Some(GrpcConfig {
+                        use_grpc: true,
+                        port,
+                        ..
+                    }),
+                ..
+            }) => Some(*port),
+            _ => None,
+        }
+    }
+
+    /// Returns the auto-funding servers configured for a given network regardless of the
+    /// auto-funding enablement, if None no configuration is found
+    pub fn get_auto_funding_servers(&self, network: Network) -> Option<AutoFundingServers> {
         match &self.farcasterd {
             Some(FarcasterdConfig {
                 auto_funding:
                     Some(AutoFundingConfig {
-                        auto_fund,
                         mainnet,
                         testnet,
                         local,
+                        ..
                     }),
-                grpc: _,
-            }) if *auto_fund => match network {
+                ..
+            }) => match network {
                 Network::Mainnet => mainnet.clone(),
                 Network::Testnet => testnet.clone(),
                 Network::Local => local.clone(),
@@ -82,6 +122,427 @@ impl Config {
         }
     }
 
+    /// Returns the default fee strategy configured for a given network, if None no default
+    /// fee strategy is set for this network
+    pub fn get_default_fee_strategy(&self, network: Network) -> Option<String> {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                default_fee_strategy:
+                    Some(FeeStrategyConfig {
+                        mainnet,
+                        testnet,
+                        local,
+                    }),
+                ..
+            }) => match network {
+                Network::Mainnet => mainnet.clone(),
+                Network::Testnet => testnet.clone(),
+                Network::Local => local.clone(),
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the interval between two periodic stats logs if enable, if None no periodic
+    /// stats are logged
+    pub fn get_stats_log_interval(&self) -> Option<Duration> {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                stats_log: Some(StatsLogConfig { enable, interval }),
+                ..
+            }) if *enable && *interval > 0 => Some(Duration::from_secs(*interval)),
+            _ => None,
+        }
+    }
+
+    /// Returns the human-readable alias of the node if set
+    pub fn get_node_alias(&self) -> Option<String> {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                alias: Some(alias), ..
+            }) => Some(alias.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns if tenant tokens are set, i.e. clients have to authenticate and only see their
+    /// own offers and swaps
+    pub fn is_multi_tenant(&self) -> bool {
+        !self.get_tenant_tokens().is_empty()
+    }
+
+    /// Returns if listeners may bind to the port, default to all ports. The invalid entries of
+    /// the allowlist are reported by the validation and allow no port.
+    pub fn is_listen_port_allowed(&self, port: u16) -> bool {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                allowed_listen_ports: Some(entries),
+                ..
+            }) if !entries.is_empty() => entries
+                .iter()
+                .filter_map(|entry| parse_port_range(entry))
+                .any(|(start, end)| (start..=end).contains(&port)),
+            _ => true,
+        }
+    }
+
+    /// Returns the confirmation counts of the funding of a swap at which swapd reports a progress
+    /// message, in increasing order, default to none
+    pub fn get_funding_progress_confirmations(&self) -> Vec<u32> {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                funding_progress_confirmations: Some(thresholds),
+                ..
+            }) => {
+                let mut thresholds = thresholds.clone();
+                thresholds.sort_unstable();
+                thresholds.dedup();
+                thresholds
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Returns the tenant token the grpc clients act as on a multi-tenant node, if set
+    pub fn get_grpc_tenant_token(&self) -> Option<String> {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                grpc:
+                    Some(GrpcConfig {
+                        tenant_token: Some(token),
+                        ..
+                    }),
+                ..
+            }) => Some(token.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the tokens of the tenants allowed to use the node
+    pub fn get_tenant_tokens(&self) -> Vec<String> {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                tenant_tokens: Some(tokens),
+                ..
+            }) => tokens.clone(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns if the tenant token may issue the node-wide requests of the node
+    pub fn is_admin_token(&self, token: &str) -> bool {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                admin_tokens: Some(tokens),
+                ..
+            }) => tokens.iter().any(|admin| admin == token),
+            _ => false,
+        }
+    }
+
+    /// Returns for how long an assembled node info can be served again to GetInfo requests,
+    /// default to 250ms
+    pub fn get_info_cache_ttl(&self) -> Duration {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                get_info_cache_ttl: Some(ttl),
+                ..
+            }) => Duration::from_millis(*ttl),
+            _ => Duration::from_millis(250),
+        }
+    }
+
+    /// Returns how many times a swap attempts to reconnect to its counterparty before its
+    /// connection is considered failed, default to 10
+    pub fn get_max_reconnect_attempts(&self) -> u32 {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                max_reconnect_attempts: Some(max),
+                ..
+            }) => *max,
+            _ => FARCASTER_DEFAULT_MAX_RECONNECT_ATTEMPTS,
+        }
+    }
+
+    /// Returns how many times the swapd of a swap is relaunched from its checkpoint after
+    /// crashing before the swap is reported failed, default to 3
+    pub fn get_max_swapd_restarts(&self) -> u32 {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                max_swapd_restarts: Some(max),
+                ..
+            }) => *max,
+            _ => FARCASTER_DEFAULT_MAX_SWAPD_RESTARTS,
+        }
+    }
+
+    /// Returns the high-water mark of the outgoing queues of the bus sockets, default to none,
+    /// i.e. the ZMQ default
+    pub fn get_zmq_send_high_water_mark(&self) -> Option<i32> {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                zmq:
+                    Some(ZmqConfig {
+                        send_high_water_mark,
+                        ..
+                    }),
+                ..
+            }) => *send_high_water_mark,
+            _ => None,
+        }
+    }
+
+    /// Returns the high-water mark of the incoming queues of the bus sockets, default to none,
+    /// i.e. the ZMQ default
+    pub fn get_zmq_receive_high_water_mark(&self) -> Option<i32> {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                zmq:
+                    Some(ZmqConfig {
+                        receive_high_water_mark,
+                        ..
+                    }),
+                ..
+            }) => *receive_high_water_mark,
+            _ => None,
+        }
+    }
+
+    /// Returns if farcasterd logs every request it sends and receives on the buses, default to
+    /// false
+    pub fn is_esb_trace_enabled(&self) -> bool {
+        matches!(
+            self.farcasterd,
+            Some(FarcasterdConfig {
+                esb_trace: Some(true),
+                ..
+            })
+        )
+    }
+
+    /// Returns if the listeners of open offers are relaunched and their offers announced again
+    /// after the listener exited, default to false
+    pub fn is_offer_rebroadcast_enabled(&self) -> bool {
+        matches!(
+            self.farcasterd,
+            Some(FarcasterdConfig {
+                rebroadcast_offers: Some(true),
+                ..
+            })
+        )
+    }
+
+    /// Returns if the peer connections announce and negotiate their protocol version, default to
+    /// false
+    pub fn is_protocol_version_exchange_enabled(&self) -> bool {
+        matches!(
+            self.farcasterd,
+            Some(FarcasterdConfig {
+                protocol_version_exchange: Some(true),
+                ..
+            })
+        )
+    }
+
+    /// Returns if farcasterd ignores the services of an unexpected type once they said hello,
+    /// default to false, i.e. they are only logged
+    pub fn is_rejecting_unexpected_services(&self) -> bool {
+        matches!(
+            self.farcasterd,
+            Some(FarcasterdConfig {
+                reject_unexpected_services: Some(true),
+                ..
+            })
+        )
+    }
+
+    /// Returns the command run whenever a swap ends, if set
+    pub fn get_swap_outcome_hook(&self) -> Option<String> {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                swap_outcome_hook: Some(hook),
+                ..
+            }) if !hook.is_empty() => Some(hook.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns if takers can commit to already open offers while farcasterd is draining,
+    /// default to false
+    pub fn is_accepting_commits_while_draining(&self) -> bool {
+        matches!(
+            self.farcasterd,
+            Some(FarcasterdConfig {
+                accept_commits_while_draining: Some(true),
+                ..
+            })
+        )
+    }
+
+    /// Returns the policy applied when a funding address is reused across swaps, default to warn
+    pub fn get_funding_address_reuse_policy(&self) -> AddressReusePolicy {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                funding_address_reuse: Some(policy),
+                ..
+            }) => *policy,
+            _ => AddressReusePolicy::Warn,
+        }
+    }
+
+    /// Returns the policy applied when taking an offer whose fee strategy cannot be evaluated,
+    /// default to reject
+    pub fn get_unsupported_fee_strategy_policy(&self) -> UnsupportedFeeStrategyPolicy {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                unsupported_fee_strategy: Some(policy),
+                ..
+            }) => *policy,
+            _ => UnsupportedFeeStrategyPolicy::Reject,
+        }
+    }
+
+    /// Returns how many clients can subscribe to the progress of a single swap, default to 100
+    pub fn get_max_progress_subscribers(&self) -> u32 {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                max_progress_subscribers: Some(max),
+                ..
+            }) => *max,
+            _ => FARCASTER_DEFAULT_MAX_PROGRESS_SUBSCRIBERS,
+        }
+    }
+
+    /// Returns the age beyond which progress messages are evicted from the progress queue of a
+    /// swap, default to none, i.e. messages are never evicted
+    pub fn get_progress_max_age(&self) -> Option<Duration> {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                progress_max_age: Some(max_age),
+                ..
+            }) => Some(Duration::from_secs(*max_age)),
+            _ => None,
+        }
+    }
+
+    /// Returns the window before the expiry of an offer in which taker commitments are rejected,
+    /// default to zero, i.e. commitments are accepted until the offer expires
+    pub fn get_offer_expiry_margin(&self) -> Duration {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                offer_expiry_margin: Some(margin),
+                ..
+            }) => Duration::from_secs(*margin),
+            _ => Duration::from_secs(0),
+        }
+    }
+
+    /// Returns how long after its startup the node rejects new offers and swaps while its syncers
+    /// and peers settle, default to zero
+    pub fn get_startup_grace_period(&self) -> Duration {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                startup_grace_period: Some(grace_period),
+                ..
+            }) => Duration::from_secs(*grace_period),
+            _ => Duration::from_secs(0),
+        }
+    }
+
+    /// Returns how long walletd can take to connect after its launch, default to 30 seconds
+    pub fn get_walletd_startup_timeout(&self) -> Duration {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                walletd_startup_timeout: Some(timeout),
+                ..
+            }) => Duration::from_secs(*timeout),
+            _ => Duration::from_secs(FARCASTER_DEFAULT_STARTUP_TIMEOUT),
+        }
+    }
+
+    /// Returns how long databased can take to connect after its launch, default to 30 seconds
+    pub fn get_databased_startup_timeout(&self) -> Duration {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                databased_startup_timeout: Some(timeout),
+                ..
+            }) => Duration::from_secs(*timeout),
+            _ => Duration::from_secs(FARCASTER_DEFAULT_STARTUP_TIMEOUT),
+        }
+    }
+
+    /// Returns how many seconds the system time can differ from the time of the latest block
+    /// reported by a syncer before the clock is considered skewed, default to 2 hours
+    pub fn get_clock_skew_threshold(&self) -> u64 {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                clock_skew_threshold: Some(threshold),
+                ..
+            }) => *threshold,
+            _ => FARCASTER_DEFAULT_CLOCK_SKEW_THRESHOLD,
+        }
+    }
+
+    /// Returns what to do when taking an offer already taken by a running swap, default to
+    /// returning the existing swap
+    pub fn get_duplicate_take_offer_policy(&self) -> DuplicateTakeOfferPolicy {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                duplicate_take_offer: Some(policy),
+                ..
+            }) => *policy,
+            _ => DuplicateTakeOfferPolicy::Existing,
+        }
+    }
+
+    /// Returns which makers offers can be taken from, default to any
+    pub fn get_take_offer_peer_policy(&self) -> TakeOfferPeerPolicy {
+        match &self.farcasterd {
+            Some(FarcasterdConfig {
+                take_offer_peers: Some(policy),
+                ..
+            }) => *policy,
+            _ => TakeOfferPeerPolicy::Any,
+        }
+    }
+
+    /// Returns the block explorer url template of a given network, default to a public explorer
+    /// for mainnet and testnet and to none for local, an empty template disables the links
+    pub fn get_explorer_url_template(&self, network: Network) -> Option<String> {
+        let configured = match &self.farcasterd {
+            Some(FarcasterdConfig {
+                explorers:
+                    Some(ExplorerConfig {
+                        mainnet,
+                        testnet,
+                        local,
+                    }),
+                ..
+            }) => match network {
+                Network::Mainnet => mainnet.clone(),
+                Network::Testnet => testnet.clone(),
+                Network::Local => local.clone(),
+            },
+            _ => None,
+        };
+        match configured {
+            Some(template) if template.trim().is_empty() => None,
+            Some(template) => Some(template),
+            None => match network {
+                Network::Mainnet => Some(FARCASTER_MAINNET_BITCOIN_EXPLORER.to_string()),
+                Network::Testnet => Some(FARCASTER_TESTNET_BITCOIN_EXPLORER.to_string()),
+                Network::Local => None,
+            },
+        }
+    }
+
+    /// Returns the block explorer url of a transaction of the given network, if an explorer is
+    /// set for the network
+    pub fn get_explorer_tx_url(&self, network: Network, txid: &Txid) -> Option<String> {
+        self.get_explorer_url_template(network)
+            .map(|template| template.replace(EXPLORER_TXID_PLACEHOLDER, &txid.to_string()))
+    }
+
     pub fn get_syncer_servers(&self, network: Network) -> Option<SyncerServers> {
         match network {
             Network::Mainnet => self.syncers.as_ref()?.mainnet.clone(),
@@ -89,6 +550,230 @@ impl Config {
             Network::Local => self.syncers.as_ref()?.local.clone(),
         }
     }
+
+    /// Replaces the syncer servers of the network, `None` unsets them
+    pub fn set_syncer_servers(&mut self, network: Network, servers: Option<SyncerServers>) {
+        let syncers = self.syncers.get_or_insert(SyncersConfig {
+            mainnet: None,
+            testnet: None,
+            local: None,
+        });
+        match network {
+            Network::Mainnet => syncers.mainnet = servers,
+            Network::Testnet => syncers.testnet = servers,
+            Network::Local => syncers.local = servers,
+        }
+    }
+
+    /// Returns the servers of a named syncer instance of the network, if it is configured
+    pub fn get_syncer_instance(
+        &self,
+        network: Network,
+        name: &str,
+    ) -> Option<SyncerInstanceServers> {
+        self.get_syncer_servers(network)?
+            .instances?
+            .get(name)
+            .cloned()
+    }
+
+    /// Checks the configuration is consistent, e.g. the servers of the configured networks are
+    /// all set and auto-funding has the credentials to reach the wallets. No server is contacted.
+    pub fn validate(&self) -> ConfigValidation {
+        let mut validation = ConfigValidation::default();
+        let networks = [Network::Mainnet, Network::Testnet, Network::Local];
+
+        let syncer_servers: Vec<(Network, SyncerServers)> = networks
+            .iter()
+            .filter_map(|network| Some((*network, self.get_syncer_servers(*network)?)))
+            .collect();
+        if syncer_servers.is_empty() {
+            validation.problem(s!("No syncer servers are configured, swaps cannot run"));
+        }
+        for (network, servers) in syncer_servers.iter() {
+            for (name, url) in [
+                ("electrum_server", &servers.electrum_server),
+                ("monero_daemon", &servers.monero_daemon),
+                ("monero_rpc_wallet", &servers.monero_rpc_wallet),
+            ] {
+                if url.trim().is_empty() {
+                    validation.problem(format!("The {} syncers have no {} set", network, name));
+                }
+            }
+            for (instance, servers) in servers.instances.iter().flatten() {
+                for (name, url) in [
+                    ("electrum_server", &servers.electrum_server),
+                    ("monero_daemon", &servers.monero_daemon),
+                    ("monero_rpc_wallet", &servers.monero_rpc_wallet),
+                ] {
+                    if url.as_ref().map_or(false, |url| url.trim().is_empty()) {
+                        validation.problem(format!(
+                            "The {} syncer instance {} sets an empty {}",
+                            network, instance, name
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(FarcasterdConfig {
+            grpc:
+                Some(GrpcConfig {
+                    use_grpc: true,
+                    port,
+                    ..
+                }),
+            ..
+        }) = &self.farcasterd
+        {
+            if *port == 0 || *port > u16::MAX as u64 {
+                validation.problem(format!("The grpc port {} is not a valid port", port));
+            }
+        }
+
+        for network in networks.iter() {
+            let servers = match self.get_auto_funding_servers(*network) {
+                Some(servers) => servers,
+                None => {
+                    if self.is_auto_funding_enable() && self.get_syncer_servers(*network).is_some()
+                    {
+                        validation.warning(format!(
+                            "Auto-funding is enabled, but no {} auto-funding servers are set, {} \
+                            swaps will not be funded automatically",
+                            network, network
+                        ));
+                    }
+                    continue;
+                }
+            };
+            if servers.bitcoin_rpc.trim().is_empty() {
+                validation.problem(format!(
+                    "The {} auto-funding servers have no bitcoin_rpc set",
+                    network
+                ));
+            }
+            if servers.monero_rpc_wallet.trim().is_empty() {
+                validation.problem(format!(
+                    "The {} auto-funding servers have no monero_rpc_wallet set",
+                    network
+                ));
+            }
+            if servers.bitcoin_cookie_path.is_none()
+                && (servers.bitcoin_rpc_user.is_none() || servers.bitcoin_rpc_pass.is_none())
+            {
+                validation.problem(format!(
+                    "The {} auto-funding servers need either bitcoin_cookie_path or both \
+                    bitcoin_rpc_user and bitcoin_rpc_pass to access the bitcoin wallet",
+                    network
+                ));
+            }
+        }
+
+        for network in networks.iter() {
+            if let Some(template) = self.get_explorer_url_template(*network) {
+                if !template.contains(EXPLORER_TXID_PLACEHOLDER) {
+                    validation.warning(format!(
+                        "The {} block explorer url \"{}\" has no {} placeholder, all the \
+                        transactions link to the same page",
+                        network, template, EXPLORER_TXID_PLACEHOLDER
+                    ));
+                }
+            }
+        }
+
+        for network in networks.iter() {
+            if let Some(fee_strategy) = self.get_default_fee_strategy(*network) {
+                if let Err(err) = FeeStrategy::<SatPerVByte>::from_str(&fee_strategy) {
+                    validation.problem(format!(
+                        "The {} default fee strategy \"{}\" is invalid: {}",
+                        network, fee_strategy, err
+                    ));
+                }
+            }
+        }
+
+        if let Some(FarcasterdConfig {
+            allowed_listen_ports: Some(entries),
+            ..
+        }) = &self.farcasterd
+        {
+            for entry in entries
+                .iter()
+                .filter(|entry| parse_port_range(entry).is_none())
+            {
+                validation.problem(format!(
+                    "The allowed listen port \"{}\" is neither a port nor a range of ports",
+                    entry
+                ));
+            }
+        }
+
+        let tenant_tokens = self.get_tenant_tokens();
+        if tenant_tokens.iter().any(|token| token.trim().is_empty()) {
+            validation.problem(s!("A tenant token is empty"));
+        }
+        let mut unique_tokens = tenant_tokens.clone();
+        unique_tokens.sort();
+        unique_tokens.dedup();
+        if unique_tokens.len() != tenant_tokens.len() {
+            validation.problem(s!("A tenant token is shared by several tenants"));
+        }
+        match self.get_grpc_tenant_token() {
+            None if self.is_multi_tenant() && self.is_grpc_enable() => validation.problem(s!(
+                "The node is multi-tenant but grpc has no tenant token, its clients are rejected"
+            )),
+            Some(token) if !tenant_tokens.contains(&token) => {
+                validation.problem(s!("The grpc tenant token is not one of the tenant tokens"))
+            }
+            _ => {}
+        }
+        if let Some(FarcasterdConfig {
+            admin_tokens: Some(admin_tokens),
+            ..
+        }) = &self.farcasterd
+        {
+            if admin_tokens
+                .iter()
+                .any(|token| !tenant_tokens.contains(token))
+            {
+                validation.problem(s!("An admin token is not one of the tenant tokens"));
+            }
+        }
+
+        if let Some(FarcasterdConfig {
+            stats_log:
+                Some(StatsLogConfig {
+                    enable: true,
+                    interval: 0,
+                }),
+            ..
+        }) = &self.farcasterd
+        {
+            validation.warning(s!(
+                "Stats logging is enabled with a zero interval, no stats are logged"
+            ));
+        }
+        for (name, hwm) in [
+            ("send", self.get_zmq_send_high_water_mark()),
+            ("receive", self.get_zmq_receive_high_water_mark()),
+        ] {
+            if matches!(hwm, Some(hwm) if hwm < 0) {
+                validation.problem(format!(
+                    "The zmq {} high-water mark is negative, services fail to set up their buses",
+                    name
+                ));
+            }
+        }
+        if self.get_walletd_startup_timeout().as_secs() == 0
+            || self.get_databased_startup_timeout().as_secs() == 0
+        {
+            validation.warning(s!(
+                "A core service startup timeout is zero, its startup will always be reported late"
+            ));
+        }
+
+        validation
+    }
 }
 
 impl Default for Config {
@@ -103,10 +788,110 @@ impl Default for Config {
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 #[serde(crate = "serde_crate")]
 pub struct FarcasterdConfig {
+    /// Sets a human-readable alias of the node, shown in the node info, default to none
+    pub alias: Option<String>,
     /// Sets the auto-funding parameters, default to no auto-fund
     pub auto_funding: Option<AutoFundingConfig>,
     /// Sets the grpc server port, if none is given, no grpc server is run
     pub grpc: Option<GrpcConfig>,
+    /// Sets the fee strategy used when an offer is made without one, per network
+    pub default_fee_strategy: Option<FeeStrategyConfig>,
+    /// Sets the block explorer url templates used to link the transactions of the swaps, per
+    /// network, default to public explorers for mainnet and testnet
+    pub explorers: Option<ExplorerConfig>,
+    /// Sets the periodic stats logging parameters, default to no periodic logging
+    pub stats_log: Option<StatsLogConfig>,
+    /// Sets the tokens of the tenants sharing the node, if none is given the node serves a
+    /// single user and clients do not authenticate
+    pub tenant_tokens: Option<Vec<String>>,
+    /// Sets the tenant tokens whose clients may issue the node-wide requests, e.g. draining the
+    /// node or pausing its syncers, default to none, i.e. no tenant may issue them
+    pub admin_tokens: Option<Vec<String>>,
+    /// Sets for how many milliseconds an assembled node info is served to subsequent GetInfo
+    /// requests, default to 250ms, zero disables the cache
+    pub get_info_cache_ttl: Option<u64>,
+    /// Sets how many times farcasterd attempts to reconnect a swap to an unreachable
+    /// counterparty before reporting the connection as failed, default to 10
+    pub max_reconnect_attempts: Option<u32>,
+    /// Sets if takers can still commit to the offers made before farcasterd started draining,
+    /// default to false
+    pub accept_commits_while_draining: Option<bool>,
+    /// Sets what to do when a swap would be funded on an address already handed out for another
+    /// swap, default to warn
+    pub funding_address_reuse: Option<AddressReusePolicy>,
+    /// Sets how many clients can subscribe to the progress of a single swap, default to 100
+    pub max_progress_subscribers: Option<u32>,
+    /// Sets what to do when taking an offer whose fee strategy the node cannot evaluate, default
+    /// to reject
+    pub unsupported_fee_strategy: Option<UnsupportedFeeStrategyPolicy>,
+    /// Sets after how many seconds the progress messages of a swap are evicted from its progress
+    /// queue, state transitions and outcomes are kept, default to never
+    pub progress_max_age: Option<u64>,
+    /// Sets how many seconds before the expiry of an offer taker commitments to it are rejected,
+    /// default to zero
+    pub offer_expiry_margin: Option<u64>,
+    /// Sets how many seconds after its startup the node rejects new offers and swaps, while its
+    /// syncers catch up and its peers reconnect, default to zero
+    pub startup_grace_period: Option<u64>,
+    /// Sets how many seconds walletd can take to connect after its launch, databased is only
+    /// launched once walletd connected, default to 30
+    pub walletd_startup_timeout: Option<u64>,
+    /// Sets how many seconds databased can take to connect after its launch, default to 30
+    pub databased_startup_timeout: Option<u64>,
+    /// Sets which makers offers can be taken from, e.g. to let a bot auto-take only from the
+    /// makers it swapped successfully with, default to any
+    pub take_offer_peers: Option<TakeOfferPeerPolicy>,
+    /// Sets what to do when taking an offer already taken by a running swap, e.g. submitted
+    /// twice by a UI, default to existing
+    pub duplicate_take_offer: Option<DuplicateTakeOfferPolicy>,
+    /// Sets how many seconds the system time can differ from the time of the latest block
+    /// reported by a syncer before a clock skew is reported, blocks may legitimately take a while
+    /// to be mined so this should span several block intervals, default to 7200
+    pub clock_skew_threshold: Option<u64>,
+    /// Sets how many times the swapd of a swap is relaunched from the latest checkpoint after it
+    /// crashed mid-swap before the swap is reported failed, zero disables the restarts, default
+    /// to 3
+    pub max_swapd_restarts: Option<u32>,
+    /// Sets the options of the ZMQ sockets of the message and control buses, default to the ZMQ
+    /// defaults
+    pub zmq: Option<ZmqConfig>,
+    /// Sets if farcasterd logs a line for every request it sends and receives on the message and
+    /// control buses, under the farcaster_node::esb_trace target, default to false
+    pub esb_trace: Option<bool>,
+    /// Sets if the listener of open offers is relaunched when it exits, e.g. after a peerd
+    /// crash, and the offers it serves are announced again to the node event subscribers,
+    /// default to false
+    pub rebroadcast_offers: Option<bool>,
+    /// Sets if farcasterd ignores every request of a service of an unexpected type, e.g. a
+    /// misconfigured service, once it said hello, default to false, i.e. it is only logged
+    pub reject_unexpected_services: Option<bool>,
+    /// Sets a command run whenever a swap ends, with the swap id, the outcome and the arbitrating
+    /// and accordant amounts as arguments, default to none
+    pub swap_outcome_hook: Option<String>,
+    /// Sets the ports the listeners of the offers may bind to, single ports or inclusive ranges,
+    /// e.g. ["9735", "9800-9810"], default to all ports
+    pub allowed_listen_ports: Option<Vec<String>>,
+    /// Sets the confirmation counts of the funding of a swap at which a progress message is sent
+    /// to the subscribers, e.g. [1, 3, 6], default to none
+    pub funding_progress_confirmations: Option<Vec<u32>>,
+    /// Sets if the peer connections announce their protocol version and swaps are refused with
+    /// peers that do not announce a supported one. Nodes predating the exchange drop the
+    /// connection on the announcement, default to false
+    pub protocol_version_exchange: Option<bool>,
+}
+
+/// The options of the ZMQ sockets of the message and control buses. Once the queue towards a
+/// service reaches the high-water mark ZMQ silently drops the messages routed to it, e.g. the
+/// progress forwarded to a slow client, until the service catches up.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(crate = "serde_crate")]
+pub struct ZmqConfig {
+    /// Maximum number of outgoing messages queued per connected service, zero means no limit,
+    /// default to the ZMQ default of 1000
+    pub send_high_water_mark: Option<i32>,
+    /// Maximum number of incoming messages queued per connected service, zero means no limit,
+    /// default to the ZMQ default of 1000
+    pub receive_high_water_mark: Option<i32>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -116,6 +901,80 @@ pub struct GrpcConfig {
     pub use_grpc: bool,
     /// Grpc port configuration
     pub port: u64,
+    /// Tenant token the grpc clients act as on a multi-tenant node, they cannot authenticate
+    /// themselves, default to none, i.e. they are rejected on a multi-tenant node
+    pub tenant_token: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(crate = "serde_crate")]
+pub struct StatsLogConfig {
+    /// Periodically log the stats and live gauges of the node
+    pub enable: bool,
+    /// Interval in seconds between two stats logs
+    pub interval: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(crate = "serde_crate", rename_all = "lowercase")]
+pub enum AddressReusePolicy {
+    /// Fund the swap without notice
+    Allow,
+    /// Fund the swap but warn the user
+    Warn,
+    /// Do not hand out the funding info of the swap
+    Deny,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(crate = "serde_crate", rename_all = "lowercase")]
+pub enum UnsupportedFeeStrategyPolicy {
+    /// Refuse to take the offer
+    Reject,
+    /// Take the offer but warn the user
+    Warn,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(crate = "serde_crate", rename_all = "lowercase")]
+pub enum DuplicateTakeOfferPolicy {
+    /// Refuse to take the offer again
+    Reject,
+    /// Report the swap already taking the offer instead of taking it again
+    Existing,
+    /// Take the offer again as a new swap
+    Allow,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(crate = "serde_crate", rename_all = "lowercase")]
+pub enum TakeOfferPeerPolicy {
+    /// Take offers from any maker
+    Any,
+    /// Take offers only from makers with more successful swaps than refunded or punished ones
+    Trusted,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(crate = "serde_crate")]
+pub struct FeeStrategyConfig {
+    /// Mainnet default fee strategy, e.g. "1 satoshi/vByte"
+    pub mainnet: Option<String>,
+    /// Testnet default fee strategy
+    pub testnet: Option<String>,
+    /// Local default fee strategy
+    pub local: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(crate = "serde_crate")]
+pub struct ExplorerConfig {
+    /// Mainnet block explorer url template, e.g. "https://mempool.space/tx/{txid}"
+    pub mainnet: Option<String>,
+    /// Testnet block explorer url template
+    pub testnet: Option<String>,
+    /// Local block explorer url template
+    pub local: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -170,6 +1029,20 @@ pub struct SyncerServers {
     pub monero_lws: Option<String>,
     /// Monero wallet directory
     pub monero_wallet_dir: Option<String>,
+    /// Named syncer instances run next to the syncers of the network, swaps pinned to an
+    /// instance are served by a dedicated syncer using its servers
+    pub instances: Option<BTreeMap<String, SyncerInstanceServers>>,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+#[serde(crate = "serde_crate")]
+pub struct SyncerInstanceServers {
+    /// Electrum server to use instead of the one of the network
+    pub electrum_server: Option<String>,
+    /// Monero daemon to use instead of the one of the network
+    pub monero_daemon: Option<String>,
+    /// Monero rpc wallet to use instead of the one of the network
+    pub monero_rpc_wallet: Option<String>,
 }
 
 impl Default for SyncersConfig {
@@ -181,6 +1054,7 @@ impl Default for SyncersConfig {
                 monero_rpc_wallet: FARCASTER_MAINNET_MONERO_RPC_WALLET.into(),
                 monero_lws: None,
                 monero_wallet_dir: None,
+                instances: None,
             }),
             testnet: Some(SyncerServers {
                 electrum_server: FARCASTER_TESTNET_ELECTRUM_SERVER.into(),
@@ -188,6 +1062,7 @@ impl Default for SyncersConfig {
                 monero_rpc_wallet: FARCASTER_TESTNET_MONERO_RPC_WALLET.into(),
                 monero_lws: None,
                 monero_wallet_dir: None,
+                instances: None,
             }),
             local: None,
         }
@@ -209,3 +1084,141 @@ pub fn parse_config(path: &str) -> Result<Config, Error> {
         Ok(config)
     }
 }
+
+/// Parses a port, e.g. "9735", or an inclusive range of ports, e.g. "9800-9810"
+fn parse_port_range(entry: &str) -> Option<(u16, u16)> {
+    let mut bounds = entry
+        .splitn(2, '-')
+        .map(|bound| bound.trim().parse::<u16>());
+    let start = bounds.next()?.ok()?;
+    let end = match bounds.next() {
+        Some(end) => end.ok()?,
+        None => start,
+    };
+    Some((start, end)).filter(|(start, end)| start <= end)
+}
+
+#[test]
+fn test_validate_config() {
+    assert_eq!(Config::default().validate(), ConfigValidation::default());
+
+    let mut config = Config::default();
+    if let Some(SyncersConfig {
+        testnet: Some(servers),
+        ..
+    }) = config.syncers.as_mut()
+    {
+        servers.electrum_server = s!("");
+    }
+    config.farcasterd = Some(FarcasterdConfig {
+        grpc: Some(GrpcConfig {
+            use_grpc: true,
+            port: 70000,
+            tenant_token: None,
+        }),
+        auto_funding: Some(AutoFundingConfig {
+            auto_fund: true,
+            mainnet: None,
+            testnet: Some(AutoFundingServers {
+                bitcoin_rpc: s!("http://localhost:18332"),
+                bitcoin_cookie_path: None,
+                bitcoin_rpc_user: Some(s!("user")),
+                bitcoin_rpc_pass: None,
+                monero_rpc_wallet: s!("http://localhost:38083"),
+            }),
+            local: None,
+        }),
+        ..Default::default()
+    });
+    let validation = config.validate();
+    // the empty electrum server, the invalid grpc port and the missing bitcoin rpc password
+    assert_eq!(validation.problems.len(), 3);
+    assert!(validation
+        .problems
+        .contains(&s!("The grpc port 70000 is not a valid port")));
+    // no mainnet auto-funding servers while auto-funding is enabled
+    assert_eq!(validation.warnings.len(), 1);
+}
+
+#[test]
+fn test_explorer_tx_url() {
+    let txid =
+        Txid::from_str("5a4ebed4a4a2e2c7d3a3a9bcbf7c71e4a0a4e8b2c7f1a2b3c4d5e6f708192a3b").unwrap();
+    let mut config = Config::default();
+    assert_eq!(
+        config.get_explorer_tx_url(Network::Testnet, &txid),
+        Some(format!("https://mempool.space/testnet/tx/{}", txid))
+    );
+    assert_eq!(config.get_explorer_tx_url(Network::Local, &txid), None);
+
+    config.farcasterd = Some(FarcasterdConfig {
+        explorers: Some(ExplorerConfig {
+            mainnet: Some(s!("")),
+            testnet: None,
+            local: Some(s!("http://localhost:3002/tx/{txid}")),
+        }),
+        ..Default::default()
+    });
+    // an empty template disables the links, an unset one falls back to the default
+    assert_eq!(config.get_explorer_tx_url(Network::Mainnet, &txid), None);
+    assert!(config
+        .get_explorer_tx_url(Network::Testnet, &txid)
+        .is_some());
+    assert_eq!(
+        config.get_explorer_tx_url(Network::Local, &txid),
+        Some(format!("http://localhost:3002/tx/{}", txid))
+    );
+}
+
+#[test]
+fn test_listen_port_allowlist() {
+    let mut config = Config::default();
+    assert!(config.is_listen_port_allowed(9735));
+
+    config.farcasterd = Some(FarcasterdConfig {
+        allowed_listen_ports: Some(vec![s!("9735"), s!("9800 - 9810"), s!("9900-9899")]),
+        ..Default::default()
+    });
+    assert!(config.is_listen_port_allowed(9735));
+    assert!(config.is_listen_port_allowed(9800));
+    assert!(config.is_listen_port_allowed(9810));
+    assert!(!config.is_listen_port_allowed(9736));
+    assert!(!config.is_listen_port_allowed(9899));
+    // the reversed range is invalid
+    assert_eq!(config.validate().problems.len(), 1);
+}
+
+#[test]
+fn test_funding_progress_confirmations() {
+    let mut config = Config::default();
+    assert!(config.get_funding_progress_confirmations().is_empty());
+
+    config.farcasterd = Some(FarcasterdConfig {
+        funding_progress_confirmations: Some(vec![6, 1, 3, 1]),
+        ..Default::default()
+    });
+    assert_eq!(config.get_funding_progress_confirmations(), vec![1, 3, 6]);
+}
+
+#[test]
+fn test_set_syncer_servers() {
+    let mut config = Config {
+        farcasterd: None,
+        syncers: None,
+    };
+    let servers = SyncerServers {
+        electrum_server: s!("ssl://localhost:50002"),
+        ..Default::default()
+    };
+    config.set_syncer_servers(Network::Local, Some(servers));
+    assert_eq!(
+        config
+            .get_syncer_servers(Network::Local)
+            .map(|s| s.electrum_server),
+        Some(s!("ssl://localhost:50002"))
+    );
+    assert!(config.get_syncer_servers(Network::Testnet).is_none());
+
+    config.set_syncer_servers(Network::Local, None);
+    assert!(config.get_syncer_servers(Network::Local).is_none());
+}
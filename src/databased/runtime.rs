@@ -14,7 +14,10 @@ use crate::Endpoints;
 use bitcoin::secp256k1::SecretKey;
 
 use crate::rpc::{
-    request::{self, Checkpoint, CheckpointEntry, CheckpointState, Failure, FailureCode, List},
+    request::{
+        self, Checkpoint, CheckpointEntry, CheckpointState, CheckpointValidation, Failure,
+        FailureCode, List, PeerReputation,
+    },
     Request, ServiceBus,
 };
 use crate::{CtlServer, Error, Service, ServiceConfig, ServiceId};
@@ -35,7 +38,53 @@ pub struct Runtime {
     database: Database,
 }
 
-impl Runtime {}
+impl Runtime {
+    fn validate_checkpoint(&mut self, swap_id: SwapId) -> Result<CheckpointEntry, String> {
+        let raw_wallet_state = self
+            .database
+            .get_checkpoint_state(&CheckpointKey {
+                swap_id,
+                service_id: ServiceId::Wallet,
+            })
+            .map_err(|err| format!("No walletd checkpoint found: {}", err))?;
+        let entry = match CheckpointState::strict_decode(std::io::Cursor::new(raw_wallet_state))
+            .map_err(|err| format!("Decoding the walletd checkpoint failed: {}", err))?
+        {
+            CheckpointState::CheckpointWallet(CheckpointWallet { wallet, .. }) => match wallet {
+                Wallet::Bob(wallet) => CheckpointEntry {
+                    swap_id,
+                    public_offer: wallet.pub_offer,
+                    trade_role: wallet.local_trade_role,
+                },
+                Wallet::Alice(wallet) => CheckpointEntry {
+                    swap_id,
+                    public_offer: wallet.pub_offer,
+                    trade_role: wallet.local_trade_role,
+                },
+            },
+            CheckpointState::CheckpointSwapd(_) => {
+                return Err(
+                    "Decoded swapd checkpoint where walletd checkpoint was stored".to_string(),
+                );
+            }
+        };
+        let raw_swap_state = self
+            .database
+            .get_checkpoint_state(&CheckpointKey {
+                swap_id,
+                service_id: ServiceId::Swap(swap_id),
+            })
+            .map_err(|err| format!("No swapd checkpoint found: {}", err))?;
+        match CheckpointState::strict_decode(std::io::Cursor::new(raw_swap_state))
+            .map_err(|err| format!("Decoding the swapd checkpoint failed: {}", err))?
+        {
+            CheckpointState::CheckpointSwapd(_) => Ok(entry),
+            CheckpointState::CheckpointWallet(_) => {
+                Err("Decoded walletd checkpoint where swapd checkpoint was stored".to_string())
+            }
+        }
+    }
+}
 
 impl CtlServer for Runtime {}
 
@@ -188,6 +237,22 @@ impl Runtime {
                 }
             }
 
+            Request::ValidateCheckpoint(swap_id) => {
+                let validation = match self.validate_checkpoint(swap_id) {
+                    Ok(entry) => CheckpointValidation::Valid(entry),
+                    Err(err) => {
+                        warn!("{} | Checkpoint is not usable: {}", swap_id, err);
+                        CheckpointValidation::Corrupt(swap_id, err)
+                    }
+                };
+                endpoints.send_to(
+                    ServiceBus::Ctl,
+                    ServiceId::Database,
+                    source,
+                    Request::CheckpointValidation(validation),
+                )?;
+            }
+
             Request::RetrieveAllCheckpointInfo => {
                 let pairs = self.database.get_checkpoint_key_value_pairs()?;
                 let checkpointed_pub_offers: List<CheckpointEntry> = pairs
@@ -339,8 +404,13 @@ impl Runtime {
                 self.database.set_offer_status(&offer, &status)?;
             }
 
-            Request::ListOffers(selector) => {
-                let offer_status_pairs = self.database.get_offers(selector)?;
+            Request::ListOffersByDirection(list_offers) => {
+                let offer_status_pairs: Vec<OfferStatusPair> = self
+                    .database
+                    .get_offers(list_offers.selector.clone())?
+                    .into_iter()
+                    .filter(|pair| list_offers.matches(&pair.offer))
+                    .collect();
                 endpoints.send_to(
                     ServiceBus::Ctl,
                     ServiceId::Database,
@@ -349,6 +419,20 @@ impl Runtime {
                 )?;
             }
 
+            Request::SetPeerReputation(peer_reputation) => {
+                self.database.set_peer_reputation(&peer_reputation)?;
+            }
+
+            Request::RetrievePeerReputations => {
+                let peer_reputations = self.database.get_peer_reputations()?;
+                endpoints.send_to(
+                    ServiceBus::Ctl,
+                    ServiceId::Database,
+                    source,
+                    Request::PeerReputationList(peer_reputations.into()),
+                )?;
+            }
+
             _ => {
                 error!("Request {} is not supported by the CTL interface", request);
             }
@@ -408,17 +492,19 @@ const LMDB_CHECKPOINTS: &str = "checkpoints";
 const LMDB_BITCOIN_ADDRESSES: &str = "bitcoin_addresses";
 const LMDB_MONERO_ADDRESSES: &str = "monero_addresses";
 const LMDB_OFFER_HISTORY: &str = "offer_history";
+const LMDB_PEER_REPUTATION: &str = "peer_reputation";
 
 impl Database {
     fn new(path: PathBuf) -> Result<Database, lmdb::Error> {
         let env = lmdb::Environment::new()
             .set_map_size(10485760 * 1024 * 64)
-            .set_max_dbs(4)
+            .set_max_dbs(5)
             .open(&path)?;
         env.create_db(Some(LMDB_CHECKPOINTS), lmdb::DatabaseFlags::empty())?;
         env.create_db(Some(LMDB_BITCOIN_ADDRESSES), lmdb::DatabaseFlags::empty())?;
         env.create_db(Some(LMDB_OFFER_HISTORY), lmdb::DatabaseFlags::empty())?;
         env.create_db(Some(LMDB_MONERO_ADDRESSES), lmdb::DatabaseFlags::empty())?;
+        env.create_db(Some(LMDB_PEER_REPUTATION), lmdb::DatabaseFlags::empty())?;
         Ok(Database(env))
     }
 
@@ -471,6 +557,33 @@ impl Database {
         Ok(res)
     }
 
+    fn set_peer_reputation(&mut self, peer_reputation: &PeerReputation) -> Result<(), lmdb::Error> {
+        let db = self.0.open_db(Some(LMDB_PEER_REPUTATION))?;
+        let mut tx = self.0.begin_rw_txn()?;
+        let mut key = vec![];
+        let _key_size = peer_reputation.node_id.strict_encode(&mut key);
+        let mut val = vec![];
+        let _val_size = peer_reputation.strict_encode(&mut val);
+        tx.put(db, &key, &val, lmdb::WriteFlags::empty())?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get_peer_reputations(&mut self) -> Result<Vec<PeerReputation>, lmdb::Error> {
+        let db = self.0.open_db(Some(LMDB_PEER_REPUTATION))?;
+        let tx = self.0.begin_ro_txn()?;
+        let mut cursor = tx.open_ro_cursor(db)?;
+        let res = cursor
+            .iter()
+            .filter_map(|(_, val)| {
+                PeerReputation::strict_decode(std::io::Cursor::new(val.to_vec())).ok()
+            })
+            .collect();
+        drop(cursor);
+        tx.abort();
+        Ok(res)
+    }
+
     fn set_bitcoin_address(
         &mut self,
         address: &bitcoin::Address,
@@ -722,4 +835,12 @@ fn test_lmdb_state() {
     assert!(offers_retrieved.len() == 2);
     assert!(offers_retrieved.contains(&status_1));
     assert!(offers_retrieved.contains(&status_2));
+
+    let mut peer_reputation = PeerReputation::new(pk.inner);
+    peer_reputation.incr_outcome(&request::Outcome::Buy);
+    database.set_peer_reputation(&peer_reputation).unwrap();
+    peer_reputation.incr_outcome(&request::Outcome::Refund);
+    database.set_peer_reputation(&peer_reputation).unwrap();
+    let peer_reputations = database.get_peer_reputations().unwrap();
+    assert!(peer_reputations.contains(&peer_reputation));
 }
@@ -82,6 +82,9 @@ pub enum Error {
     /// Invalid walletd token error
     InvalidToken,
 
+    /// Unable to launch {0}, no executable binary found at {1}
+    BinaryNotFound(String, String),
+
     /// Syncer
     #[display(inner)]
     #[from(SyncerError)]
@@ -2,6 +2,7 @@ use microservices::esb;
 
 use crate::rpc::Request;
 use crate::rpc::ServiceBus;
+use crate::service::send_traced;
 use crate::Endpoints;
 use crate::ServiceId;
 
@@ -49,8 +50,13 @@ impl<'esb> Event<'esb> {
 
     /// Finalizes event processing by sending reply request via CTL message bus
     pub fn complete_ctl(self, request: Request) -> Result<(), esb::Error<ServiceId>> {
-        self.endpoints
-            .send_to(ServiceBus::Ctl, self.service, self.source, request)
+        send_traced(
+            self.endpoints,
+            ServiceBus::Ctl,
+            self.service,
+            self.source,
+            request,
+        )
     }
 
     /// Finalizes event processing by sending reply request via CTL message bus to a specific
@@ -60,8 +66,13 @@ impl<'esb> Event<'esb> {
         service: ServiceId,
         request: Request,
     ) -> Result<(), esb::Error<ServiceId>> {
-        self.endpoints
-            .send_to(ServiceBus::Ctl, self.service, service, request)
+        send_traced(
+            self.endpoints,
+            ServiceBus::Ctl,
+            self.service,
+            service,
+            request,
+        )
     }
 
     /// Sends reply request via CTL message bus to a specific service (different from the event
@@ -71,8 +82,13 @@ impl<'esb> Event<'esb> {
         service: ServiceId,
         request: Request,
     ) -> Result<(), esb::Error<ServiceId>> {
-        self.endpoints
-            .send_to(ServiceBus::Ctl, self.service.clone(), service, request)
+        send_traced(
+            self.endpoints,
+            ServiceBus::Ctl,
+            self.service.clone(),
+            service,
+            request,
+        )
     }
 
     /// Send reply request via MSG message bus to a specific service (different from the event originating service)
@@ -81,13 +97,19 @@ impl<'esb> Event<'esb> {
         service: ServiceId,
         request: Request,
     ) -> Result<(), esb::Error<ServiceId>> {
-        self.endpoints
-            .send_to(ServiceBus::Msg, self.service.clone(), service, request)
+        send_traced(
+            self.endpoints,
+            ServiceBus::Msg,
+            self.service.clone(),
+            service,
+            request,
+        )
     }
 
     /// Forwards a request through bus to service (new source is the service forwarding)
     pub fn forward_msg(&mut self, service: ServiceId) -> Result<(), esb::Error<ServiceId>> {
-        self.endpoints.send_to(
+        send_traced(
+            self.endpoints,
             ServiceBus::Msg,
             self.service.clone(),
             service,
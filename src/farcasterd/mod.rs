@@ -14,6 +14,7 @@
 
 #[cfg(feature = "shell")]
 mod opts;
+mod reachability;
 mod runtime;
 mod syncer_state_machine;
 mod trade_state_machine;
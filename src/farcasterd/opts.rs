@@ -38,6 +38,11 @@ pub struct Opts {
         value_hint = ValueHint::FilePath
     )]
     pub config: String,
+
+    /// Start in safe mode: only walletd and databased are launched, grpcd and the syncers are
+    /// not, and the requests needing them are rejected
+    #[clap(long, global = true, env = "FARCASTER_SAFE_MODE")]
+    pub safe_mode: bool,
 }
 
 impl Opts {
@@ -0,0 +1,106 @@
+//! Reachability test of the addresses takers use to reach the node's listeners. The node connects
+//! to the addresses itself, through the Tor proxy if one is configured, so a failed connection
+//! hints at a NAT or firewall in the way, while a successful one does not rule out a NAT that
+//! only allows local hairpin connections.
+
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use internet2::addr::InetSocketAddr;
+
+/// Maximum time spent connecting to a single address, or waiting on the proxy
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Attempts a TCP connection to the address. Onion addresses carry no port, they are tested on
+/// the port of the listener serving them and require the Tor proxy. An unspecified address, i.e.
+/// a listener binding all interfaces that no offer announces a public address for, is reported
+/// as not testable, connecting to it would only reach the node itself.
+pub fn check_reachability(
+    address: InetSocketAddr,
+    listener_port: u16,
+    tor_proxy: Option<SocketAddr>,
+) -> Result<(), String> {
+    let (host, port) = match (onion_host(address), address.port()) {
+        (Some(onion), _) => (onion, listener_port),
+        (None, Some(port)) => (address.address().to_string(), port),
+        (None, None) => return Err(format!("{} has no port to connect to", address)),
+    };
+    if host
+        .parse::<IpAddr>()
+        .map_or(false, |ip| ip.is_unspecified())
+    {
+        return Err(format!(
+            "{} binds all interfaces and has no public address to test, announce one in an offer",
+            address
+        ));
+    }
+    match tor_proxy {
+        Some(proxy) => socks5_connect(proxy, &host, port),
+        None if onion_host(address).is_some() => Err(s!(
+            "onion addresses can only be tested through the Tor proxy"
+        )),
+        None => {
+            let socket_addr = (host.as_str(), port)
+                .to_socket_addrs()
+                .map_err(|err| err.to_string())?
+                .next()
+                .ok_or_else(|| format!("{} does not resolve", host))?;
+            TcpStream::connect_timeout(&socket_addr, CONNECT_TIMEOUT)
+                .map(|_| ())
+                .map_err(|err| err.to_string())
+        }
+    }
+}
+
+#[cfg(feature = "tor")]
+fn onion_host(address: InetSocketAddr) -> Option<String> {
+    address
+        .address()
+        .onion_address()
+        .map(|onion| format!("{}.onion", onion.get_address_without_dot_onion()))
+}
+
+#[cfg(not(feature = "tor"))]
+fn onion_host(_address: InetSocketAddr) -> Option<String> {
+    None
+}
+
+/// Connects to host:port through a SOCKS5 proxy without authentication, the host is passed as
+/// a domain name so the proxy resolves it
+fn socks5_connect(proxy: SocketAddr, host: &str, port: u16) -> Result<(), String> {
+    let io_err = |err: std::io::Error| format!("proxy {}: {}", proxy, err);
+    let mut stream = TcpStream::connect_timeout(&proxy, CONNECT_TIMEOUT).map_err(io_err)?;
+    stream
+        .set_read_timeout(Some(CONNECT_TIMEOUT))
+        .map_err(io_err)?;
+    stream
+        .set_write_timeout(Some(CONNECT_TIMEOUT))
+        .map_err(io_err)?;
+
+    // greeting: version 5, one method, no authentication
+    stream.write_all(&[0x05, 0x01, 0x00]).map_err(io_err)?;
+    let mut method = [0u8; 2];
+    stream.read_exact(&mut method).map_err(io_err)?;
+    if method != [0x05, 0x00] {
+        return Err(format!("proxy {} refused unauthenticated access", proxy));
+    }
+
+    // connect request to a domain name
+    if host.len() > u8::MAX as usize {
+        return Err(format!("host name {} is too long", host));
+    }
+    let mut request = vec![0x05, 0x01, 0x00, 0x03, host.len() as u8];
+    request.extend_from_slice(host.as_bytes());
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).map_err(io_err)?;
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply).map_err(io_err)?;
+    match reply[1] {
+        0x00 => Ok(()),
+        code => Err(format!(
+            "proxy {} could not connect to {}:{}, SOCKS5 reply code {}",
+            proxy, host, port, code
+        )),
+    }
+}
@@ -4,12 +4,15 @@ use farcaster_core::blockchain::{Blockchain, Network};
 use crate::{
     error::Error,
     event::{Event, StateMachine},
-    rpc::Request,
-    syncerd::{Event as SyncerEvent, SweepAddress, SweepAddressAddendum, Task, TaskId},
+    rpc::{Failure, FailureCode, Request},
+    syncerd::{
+        Event as SyncerEvent, GetSweepStatus, SweepAddress, SweepAddressAddendum, SweepStatus,
+        SweepTaskStatus, Task, TaskId,
+    },
     ServiceId,
 };
 
-use super::runtime::{syncer_up, Runtime};
+use super::runtime::{syncer_up, Runtime, SyncerUp};
 
 /// State machine for making a syncer request from and to a client.
 /// State machine automaton:
@@ -42,8 +45,9 @@ pub enum SyncerStateMachine {
     #[display("Awaiting Syncer")]
     AwaitingSyncer(AwaitingSyncer),
 
-    /// AwaitingSyncerRequest state - transitions to None on success. Transition
-    /// to None triggers a response back to the client.
+    /// AwaitingSyncerRequest state - transitions to None on success, or on a
+    /// queried sweep status reporting the sweep completed or unknown to the
+    /// syncer. Transition to None triggers a response back to the client.
     #[display("Awaiting Syncer Request")]
     AwaitingSyncerRequest(AwaitingSyncerRequest),
 }
@@ -59,6 +63,7 @@ pub struct AwaitingSyncerRequest {
     source: ServiceId,
     syncer: ServiceId,
     syncer_task_id: TaskId,
+    status_requester: Option<ServiceId>,
 }
 
 impl StateMachine<Runtime, Error> for SyncerStateMachine {
@@ -144,28 +149,43 @@ fn attempt_transition_to_awaiting_syncer_or_awaiting_syncer_request(
             runtime.syncer_task_counter += 1;
 
             // check if a monero syncer is up
-            if let Some(service_id) = syncer_up(
+            match syncer_up(
                 &mut runtime.spawning_services,
                 &mut runtime.registered_services,
                 blockchain,
                 network,
                 &runtime.config,
+                &none!(),
             )? {
-                event.complete_ctl_service(service_id, Request::SyncerTask(syncer_task))?;
-                Ok(Some(SyncerStateMachine::AwaitingSyncerRequest(
-                    AwaitingSyncerRequest {
+                SyncerUp::Ready(syncer) => {
+                    event.complete_ctl_service(syncer.clone(), Request::SyncerTask(syncer_task))?;
+                    Ok(Some(SyncerStateMachine::AwaitingSyncerRequest(
+                        AwaitingSyncerRequest {
+                            source,
+                            syncer_task_id,
+                            syncer,
+                            status_requester: None,
+                        },
+                    )))
+                }
+                SyncerUp::Spawning(syncer) => {
+                    Ok(Some(SyncerStateMachine::AwaitingSyncer(AwaitingSyncer {
                         source,
+                        syncer,
+                        syncer_task,
                         syncer_task_id,
-                        syncer: ServiceId::Syncer(blockchain, network),
-                    },
-                )))
-            } else {
-                Ok(Some(SyncerStateMachine::AwaitingSyncer(AwaitingSyncer {
-                    source,
-                    syncer: ServiceId::Syncer(blockchain, network),
-                    syncer_task: syncer_task,
-                    syncer_task_id,
-                })))
+                    })))
+                }
+                SyncerUp::SpawnFailed(syncer, reason) => {
+                    event.complete_ctl_service(
+                        source,
+                        Request::Failure(Failure {
+                            code: FailureCode::Unknown,
+                            info: format!("Cannot sweep, failed to launch {}: {}", syncer, reason),
+                        }),
+                    )?;
+                    Ok(None)
+                }
             }
         }
 
@@ -192,6 +212,7 @@ fn attempt_transition_to_awaiting_syncer_request(
                     source,
                     syncer_task_id,
                     syncer,
+                    status_requester: None,
                 },
             )))
         }
@@ -227,47 +248,65 @@ fn attempt_transition_to_end(
         syncer_task_id,
         source,
         syncer,
+        status_requester,
     } = awaiting_syncer_request;
     match (event.request.clone(), event.source.clone()) {
         (Request::SyncerEvent(SyncerEvent::SweepSuccess(success)), syncer_id)
             if syncer == syncer_id && success.id == syncer_task_id =>
         {
-            if let Some(Some(txid)) = success
-                .txids
-                .clone()
-                .pop()
-                .map(|txid| bitcoin::Txid::from_slice(&txid).ok())
-            {
-                event.send_ctl_service(
+            event.send_ctl_service(source, Request::String(sweep_outcome(success.txids)))?;
+            Ok(None)
+        }
+        (Request::QuerySweepStatus(task_id), requester) if task_id == syncer_task_id => {
+            event.send_ctl_service(
+                syncer.clone(),
+                Request::SyncerTask(Task::GetSweepStatus(GetSweepStatus { id: task_id })),
+            )?;
+            Ok(Some(SyncerStateMachine::AwaitingSyncerRequest(
+                AwaitingSyncerRequest {
+                    syncer_task_id,
                     source,
-                    Request::String(format!(
-                        "Successfully sweeped address. Transaction Id: {}.",
-                        txid.to_hex()
-                    )),
-                )?;
-            } else {
-                event.send_ctl_service(source, Request::String("Nothing to sweep.".to_string()))?;
-            }
-
-            runtime.registered_services = runtime
-                .registered_services
-                .clone()
-                .drain()
-                .filter(|service| {
-                    if let ServiceId::Syncer(..) = service {
-                        if !runtime.syncer_has_client(service) {
-                            info!("Terminating {}", service);
-                            event
-                                .send_ctl_service(service.clone(), Request::Terminate)
-                                .is_err()
-                        } else {
-                            true
-                        }
-                    } else {
-                        true
+                    syncer,
+                    status_requester: Some(requester),
+                },
+            )))
+        }
+        (Request::SyncerEvent(SyncerEvent::SweepStatus(SweepStatus { id, status })), syncer_id)
+            if syncer == syncer_id && id == syncer_task_id =>
+        {
+            let reply = match status {
+                SweepTaskStatus::Pending => {
+                    if let Some(requester) = status_requester {
+                        event.send_ctl_service(
+                            requester,
+                            Request::String("The sweep is still pending.".to_string()),
+                        )?;
                     }
-                })
-                .collect();
+                    return Ok(Some(SyncerStateMachine::AwaitingSyncerRequest(
+                        AwaitingSyncerRequest {
+                            syncer_task_id,
+                            source,
+                            syncer,
+                            status_requester: None,
+                        },
+                    )));
+                }
+                SweepTaskStatus::Completed(txids) => Request::String(sweep_outcome(txids)),
+                SweepTaskStatus::Unknown => Request::Failure(Failure {
+                    code: FailureCode::Unknown,
+                    info: format!(
+                        "The sweep task is unknown to {}, it was lost and has to be sent again.",
+                        syncer
+                    ),
+                }),
+            };
+            // the client that requested the sweep may be long gone by now
+            if let Err(err) = event.send_ctl_service(source.clone(), reply.clone()) {
+                debug!("Could not report the sweep outcome to {}: {}", source, err);
+            }
+            if let Some(requester) = status_requester.filter(|requester| *requester != source) {
+                event.send_ctl_service(requester, reply)?;
+            }
             Ok(None)
         }
         (req, source) => {
@@ -288,8 +327,23 @@ fn attempt_transition_to_end(
                     syncer_task_id,
                     source,
                     syncer,
+                    status_requester,
                 },
             )))
         }
     }
 }
+
+fn sweep_outcome(mut txids: Vec<Vec<u8>>) -> String {
+    if let Some(Some(txid)) = txids
+        .pop()
+        .map(|txid| bitcoin::Txid::from_slice(&txid).ok())
+    {
+        format!(
+            "Successfully sweeped address. Transaction Id: {}.",
+            txid.to_hex()
+        )
+    } else {
+        "Nothing to sweep.".to_string()
+    }
+}
@@ -1,10 +1,18 @@
-use crate::farcasterd::runtime::{launch, launch_swapd, syncer_up, Runtime};
+use crate::config::{
+    DuplicateTakeOfferPolicy, TakeOfferPeerPolicy, UnsupportedFeeStrategyPolicy,
+    FARCASTER_DEFAULT_FEE_STRATEGY,
+};
+use crate::farcasterd::runtime::{launch_swapd, syncer_service_id, syncer_up, Runtime};
 use crate::rpc::request::{
-    BitcoinAddress, BitcoinFundingInfo, CheckpointEntry, FundingInfo, InitSwap, LaunchSwap,
-    MadeOffer, MoneroAddress, MoneroFundingInfo, Msg, OfferInfo, OfferStatus, OfferStatusPair,
-    ProtoPublicOffer, PubOffer, TakeCommit, TookOffer,
+    BitcoinAddress, BitcoinFundingInfo, CheckpointEntry, CheckpointValidation, FundingInfo,
+    InitSwap, LaunchSwap, MadeOffer, MoneroAddress, MoneroFundingInfo, Msg, NodeEvent, OfferInfo,
+    OfferStatus, OfferStatusPair, Progress, ProtoPublicOffer, PubOffer, RevokeOffer, SwapInfo,
+    SwapStarted, SwapTimeline, SwapTxid, SyncerServersOverride, TakeCommit, TookOffer,
+    TradeStateDump,
 };
-use crate::rpc::{Failure, FailureCode};
+use crate::rpc::{Failure, FailureCode, ServiceBus};
+use crate::service::{send_traced, Endpoints};
+use crate::syncerd::Event as SyncerEvent;
 use crate::LogStyle;
 use crate::{
     error::Error,
@@ -12,93 +20,92 @@ use crate::{
     rpc::request::{Outcome, Request},
     ServiceId,
 };
-use bitcoin::hashes::hex::ToHex;
-use farcaster_core::blockchain::Blockchain;
-use farcaster_core::role::TradeRole;
+use bitcoin::secp256k1::PublicKey;
+use farcaster_core::bitcoin::fee::SatPerVByte;
+use farcaster_core::blockchain::{Blockchain, FeeStrategy, Network};
+use farcaster_core::role::{SwapRole, TradeRole};
 use farcaster_core::swap::{btcxmr::PublicOffer, SwapId};
-use internet2::addr::{NodeAddr, NodeId};
+use farcaster_core::transaction::TxLabel;
+use internet2::addr::{InetSocketAddr, NodeAddr, NodeId};
 use microservices::esb::Handler;
 use std::str::FromStr;
+use std::time::{Duration, SystemTime};
 
 /// State machine for launching a swap and cleaning up once done.
 ///
 /// State machine automaton:
 /// ```ignore
-/// StartRestore    StartTaker  StartMaker
-///       |             |            |
-///       |             |            V
-///       |             |        MakeOffer
-///       |             |            |
-///       |             V            V
-///       |         TakeOffer   TakerCommit
-///       |             |____________|
-///       |                   |
-///       V                   V
-/// RestoringSwapd      SwapdLaunched
-///       |___________________|
-///                 |
-///                 V
-///            SwapdRunning
-///                 |
-///                 V
-///                End
+///   StartRestore      StartTaker  StartMaker
+///         |               |            |
+///         V               |            V
+/// ValidatingCheckpoint    |        MakeOffer
+///         |               |            |
+///         |               V            V
+///         |           TakeOffer   TakerCommit
+///         |               |____________|
+///         |                     |
+///         V                     V
+///   RestoringSwapd        SwapdLaunched
+///         |_____________________|
+///                    |
+///                    V
+///               SwapdRunning
+///                    |
+///                    V
+///                   End
 /// ```
-#[derive(Display)]
 pub enum TradeStateMachine {
     /// StartMaker state - transitions to MakeOffer on cli request or None on
     /// failure. Transition to MakeOffer triggers a listen peerd launch (if
     /// required), sends SetOfferStatus to databased, sends MadeOffer back to cli.
-    #[display("Start Maker")]
     StartMaker,
 
     /// StartTaker state - transitions to TakeOffer on cli request or None on
     /// failure. Transition to TakeOffer triggers a connect peerd launch (if
     /// required), sends TakeOffer to walletd, sends TookOffer back to cli
-    #[display("Start Taker")]
     StartTaker,
 
-    /// StartRestore - transitions to RestoringSwapd on cli request or None on
-    /// failure. Transition to RestoringSwapd triggers launching swapd and
-    /// syncers, sends reply to cli.
-    #[display("Start Restore")]
+    /// StartRestore - transitions to ValidatingCheckpoint on cli request or
+    /// None on failure. Transition to ValidatingCheckpoint triggers sending
+    /// ValidateCheckpoint to databased.
     StartRestore,
 
+    /// ValidatingCheckpoint state - transitions to RestoringSwapd once
+    /// databased reports the checkpoint as usable or None if it is missing or
+    /// corrupt. Transition to RestoringSwapd triggers launching swapd and
+    /// syncers, sends reply to cli.
+    ValidatingCheckpoint(ValidatingCheckpoint),
+
     /// MakeOffer state - transitions to TakerCommit once TakerCommit is
     /// received from a counterpary or None if RevokeOffer is received from the
     /// user. Transition to TakerCommit triggers sending Bitcoin and Monero
     /// addresses to walletd as well as forwarding TakerCommit to walletd and
     /// sending SetOfferStatus to databased.
-    #[display("Make Offer")]
     MakeOffer(MakeOffer),
 
     /// TakerCommit state - transitions to SwapdLaunched once LaunchSwap is
     /// received from walletd. Transition to SwapdLaunched triggers launch
-    /// swapd.
-    #[display("Taker Commit")]
+    /// swapd and notifies the client of the offer with SwapStarted.
     TakerCommit(TakerCommit),
 
     /// TakeOffer state - transitions to SwapdLaunched once LaunchSwap is
     /// received from walletd. Transition to SwapdLaunched triggers launch
-    /// swapd.
-    #[display("Take Offer")]
+    /// swapd and notifies the client of the offer with SwapStarted.
     TakeOffer(TakeOffer),
 
     /// RestoringSwapd state - transitions to SwapdRunning once the various
     /// Hello's are received. Transition to RestoringSwapd triggers
     /// RestoreCheckpoint request to database.
-    #[display("Restoring Swapd")]
     RestoringSwapd(RestoringSwapd),
 
     /// SwapdLaunched state - transitions to SwapRunning once the various
     /// Hello's are reiceved. Transition to SwapRunning triggers sending
     /// TakeSwap to swapd
-    #[display("Swapd Launched")]
     SwapdLaunched(SwapdLaunched),
 
     /// SwapdRunning state - transitions to None once the SwapOutcome is received
     /// from its swapd. Transition to None triggers clean up. Handles requests
     /// affecting the connection status and funding status of a swap.
-    #[display("Swapd Running")]
     SwapdRunning(SwapdRunning),
 }
 
@@ -106,11 +113,17 @@ pub struct MakeOffer {
     public_offer: PublicOffer,
     arb_addr: bitcoin::Address,
     acc_addr: monero::Address,
+    bind_addr: InetSocketAddr,
+    syncer_servers: SyncerServersOverride,
+    client: ServiceId,
+    expires_at: Option<SystemTime>,
 }
 
 pub struct TakerCommit {
     peerd: ServiceId,
     public_offer: PublicOffer,
+    syncer_servers: SyncerServersOverride,
+    client: ServiceId,
 }
 
 pub struct TakeOffer {
@@ -118,25 +131,45 @@ pub struct TakeOffer {
     arb_addr: bitcoin::Address,
     acc_addr: monero::Address,
     peerd: ServiceId,
+    syncer_servers: SyncerServersOverride,
+    client: ServiceId,
 }
 
 pub struct SwapdLaunched {
     peerd: ServiceId,
     public_offer: PublicOffer,
     swap_id: SwapId,
+    arbitrating_syncer: ServiceId,
+    accordant_syncer: ServiceId,
     arbitrating_syncer_up: Option<ServiceId>,
     accordant_syncer_up: Option<ServiceId>,
     swapd_up: bool,
     init_swap: InitSwap,
     local_trade_role: TradeRole,
+    launched_at: SystemTime,
+    nickname: Option<String>,
+}
+
+pub struct ValidatingCheckpoint {
+    public_offer: PublicOffer,
+    trade_role: TradeRole,
+    swap_id: SwapId,
+    client: ServiceId,
 }
 
 pub struct RestoringSwapd {
     public_offer: PublicOffer,
     swap_id: SwapId,
+    arbitrating_syncer: ServiceId,
+    accordant_syncer: ServiceId,
     arbitrating_syncer_up: Option<ServiceId>,
     accordant_syncer_up: Option<ServiceId>,
     swapd_up: bool,
+    local_trade_role: TradeRole,
+    launched_at: SystemTime,
+    nickname: Option<String>,
+    // whether a client requested the restore, as opposed to a relaunch of a crashed swapd
+    requested: bool,
 }
 
 pub struct SwapdRunning {
@@ -148,6 +181,24 @@ pub struct SwapdRunning {
     connected: bool,
     funding_info: Option<FundingInfo>,
     auto_funded: bool,
+    funds_locked: bool,
+    local_trade_role: TradeRole,
+    reconnect_attempts: u32,
+    txids: Vec<SwapTxid>,
+    timeline: SwapTimeline,
+    launched_at: SystemTime,
+    nickname: Option<String>,
+}
+
+/// Displays the state name, followed by the nickname of the swap if one is set
+impl std::fmt::Display for TradeStateMachine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state_name();
+        match self.nickname() {
+            Some(nickname) => write!(f, "{} [{}]", state, nickname),
+            None => write!(f, "{}", state),
+        }
+    }
 }
 
 impl StateMachine<Runtime, Error> for TradeStateMachine {
@@ -156,7 +207,14 @@ impl StateMachine<Runtime, Error> for TradeStateMachine {
             TradeStateMachine::StartTaker => attempt_transition_to_take_offer(event, runtime),
             TradeStateMachine::StartMaker => attempt_transition_to_make_offer(event, runtime),
             TradeStateMachine::StartRestore => {
-                attempt_transition_to_restoring_swapd(event, runtime)
+                attempt_transition_to_validating_checkpoint(event, runtime)
+            }
+            TradeStateMachine::ValidatingCheckpoint(validating_checkpoint) => {
+                attempt_transition_from_validating_checkpoint_to_restoring_swapd(
+                    event,
+                    runtime,
+                    validating_checkpoint,
+                )
             }
             TradeStateMachine::MakeOffer(make_offer) => {
                 attempt_transition_to_taker_committed(event, runtime, make_offer)
@@ -189,6 +247,138 @@ impl StateMachine<Runtime, Error> for TradeStateMachine {
 }
 
 impl TradeStateMachine {
+    /// The name of the state, without the nickname of the swap
+    pub fn state_name(&self) -> &'static str {
+        match self {
+            TradeStateMachine::StartMaker => "Start Maker",
+            TradeStateMachine::StartTaker => "Start Taker",
+            TradeStateMachine::StartRestore => "Start Restore",
+            TradeStateMachine::ValidatingCheckpoint(_) => "Validating Checkpoint",
+            TradeStateMachine::MakeOffer(_) => "Make Offer",
+            TradeStateMachine::TakerCommit(_) => "Taker Commit",
+            TradeStateMachine::TakeOffer(_) => "Take Offer",
+            TradeStateMachine::RestoringSwapd(_) => "Restoring Swapd",
+            TradeStateMachine::SwapdLaunched(_) => "Swapd Launched",
+            TradeStateMachine::SwapdRunning(_) => "Swapd Running",
+        }
+    }
+
+    pub fn nickname(&self) -> Option<&String> {
+        match self {
+            TradeStateMachine::SwapdLaunched(SwapdLaunched { nickname, .. })
+            | TradeStateMachine::RestoringSwapd(RestoringSwapd { nickname, .. })
+            | TradeStateMachine::SwapdRunning(SwapdRunning { nickname, .. }) => nickname.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Sets the nickname of the swap shown in the logs, returns false if the state machine has
+    /// no swap yet
+    pub fn set_nickname(&mut self, new_nickname: Option<String>) -> bool {
+        match self {
+            TradeStateMachine::SwapdLaunched(SwapdLaunched { nickname, .. })
+            | TradeStateMachine::RestoringSwapd(RestoringSwapd { nickname, .. })
+            | TradeStateMachine::SwapdRunning(SwapdRunning { nickname, .. }) => {
+                *nickname = new_nickname;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the node id of the counterparty of a swap, unknown for restored maker swaps
+    pub fn counterparty_node_id(&self) -> Option<PublicKey> {
+        match self {
+            TradeStateMachine::SwapdLaunched(SwapdLaunched {
+                local_trade_role,
+                public_offer,
+                peerd,
+                ..
+            })
+            | TradeStateMachine::SwapdRunning(SwapdRunning {
+                local_trade_role,
+                public_offer,
+                peerd,
+                ..
+            }) => counterparty_node_id(*local_trade_role, public_offer, peerd),
+            TradeStateMachine::RestoringSwapd(RestoringSwapd {
+                local_trade_role,
+                public_offer,
+                ..
+            }) => counterparty_node_id(*local_trade_role, public_offer, &ServiceId::Loopback),
+            _ => None,
+        }
+    }
+
+    /// Binds a running swap to another connection, returns the previous one. The new connection
+    /// is registered, so the swap is connected again.
+    pub fn rebind_connection(&mut self, new_peerd: ServiceId) -> Option<ServiceId> {
+        match self {
+            TradeStateMachine::SwapdRunning(SwapdRunning {
+                peerd,
+                connected,
+                reconnect_attempts,
+                ..
+            }) => {
+                *connected = true;
+                *reconnect_attempts = 0;
+                Some(std::mem::replace(peerd, new_peerd))
+            }
+            _ => None,
+        }
+    }
+
+    /// Reconciles the offer and trade role of a swap with its checkpoint, the checkpoint is
+    /// authoritative. Returns the discrepancies found, none if the state machine has no swap.
+    pub fn resync(&mut self, entry: &CheckpointEntry) -> Vec<String> {
+        let (public_offer, local_trade_role) = match self {
+            TradeStateMachine::SwapdLaunched(SwapdLaunched {
+                public_offer,
+                local_trade_role,
+                ..
+            })
+            | TradeStateMachine::RestoringSwapd(RestoringSwapd {
+                public_offer,
+                local_trade_role,
+                ..
+            })
+            | TradeStateMachine::SwapdRunning(SwapdRunning {
+                public_offer,
+                local_trade_role,
+                ..
+            }) => (public_offer, local_trade_role),
+            _ => return vec![],
+        };
+        let mut discrepancies = vec![];
+        if *public_offer != entry.public_offer {
+            discrepancies.push(format!(
+                "offer {} replaced by the checkpointed offer {}",
+                public_offer.id(),
+                entry.public_offer.id()
+            ));
+            *public_offer = entry.public_offer.clone();
+        }
+        if *local_trade_role != entry.trade_role {
+            discrepancies.push(format!(
+                "trade role {} replaced by the checkpointed trade role {}",
+                local_trade_role, entry.trade_role
+            ));
+            *local_trade_role = entry.trade_role;
+        }
+        discrepancies
+    }
+
+    /// Returns the offer of a state machine waiting for walletd to launch its swap
+    pub fn awaiting_launch_offer(&self) -> Option<PublicOffer> {
+        match self {
+            TradeStateMachine::TakeOffer(TakeOffer { public_offer, .. })
+            | TradeStateMachine::TakerCommit(TakerCommit { public_offer, .. }) => {
+                Some(public_offer.clone())
+            }
+            _ => None,
+        }
+    }
+
     pub fn open_offer(&self) -> Option<PublicOffer> {
         if let TradeStateMachine::MakeOffer(MakeOffer { public_offer, .. }) = self {
             Some(public_offer.clone())
@@ -197,6 +387,31 @@ impl TradeStateMachine {
         }
     }
 
+    pub fn open_offer_expiry(&self) -> Option<SystemTime> {
+        if let TradeStateMachine::MakeOffer(MakeOffer { expires_at, .. }) = self {
+            *expires_at
+        } else {
+            None
+        }
+    }
+
+    pub fn open_offer_bind_addr(&self) -> Option<InetSocketAddr> {
+        if let TradeStateMachine::MakeOffer(MakeOffer { bind_addr, .. }) = self {
+            Some(*bind_addr)
+        } else {
+            None
+        }
+    }
+
+    pub fn validating_checkpoint(&self) -> Option<SwapId> {
+        if let TradeStateMachine::ValidatingCheckpoint(ValidatingCheckpoint { swap_id, .. }) = self
+        {
+            Some(*swap_id)
+        } else {
+            None
+        }
+    }
+
     pub fn consumed_offer(&self) -> Option<PublicOffer> {
         match self {
             TradeStateMachine::TakeOffer(TakeOffer { public_offer, .. }) => {
@@ -227,21 +442,199 @@ impl TradeStateMachine {
         }
     }
 
+    /// Info about the swap of this state machine as far as farcasterd knows it, the protocol
+    /// message stats are only known to swapd
+    pub fn swap_info(&self) -> Option<SwapInfo> {
+        let (swap_id, peerd, public_offer, launched_at) = match self {
+            TradeStateMachine::SwapdLaunched(SwapdLaunched {
+                swap_id,
+                peerd,
+                public_offer,
+                launched_at,
+                ..
+            })
+            | TradeStateMachine::SwapdRunning(SwapdRunning {
+                swap_id,
+                peerd,
+                public_offer,
+                launched_at,
+                ..
+            }) => (swap_id, Some(peerd), public_offer, launched_at),
+            TradeStateMachine::RestoringSwapd(RestoringSwapd {
+                swap_id,
+                public_offer,
+                launched_at,
+                ..
+            }) => (swap_id, None, public_offer, launched_at),
+            _ => return None,
+        };
+        let maker_peer = match peerd {
+            Some(ServiceId::Peer(addr)) => vec![addr.clone()],
+            _ => vec![],
+        };
+        Some(SwapInfo {
+            swap_id: Some(*swap_id),
+            maker_peer,
+            uptime: SystemTime::now()
+                .duration_since(*launched_at)
+                .unwrap_or_else(|_| Duration::from_secs(0)),
+            since: launched_at
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_else(|_| Duration::from_secs(0))
+                .as_secs(),
+            public_offer: public_offer.clone(),
+            message_stats: None,
+        })
+    }
+
+    /// Returns the milestones a swap reached so far, only its initiation for a swap that is
+    /// not running yet
+    pub fn swap_timeline(&self) -> Option<SwapTimeline> {
+        match self {
+            TradeStateMachine::SwapdLaunched(SwapdLaunched {
+                swap_id,
+                launched_at,
+                ..
+            })
+            | TradeStateMachine::RestoringSwapd(RestoringSwapd {
+                swap_id,
+                launched_at,
+                ..
+            }) => Some(SwapTimeline::new(*swap_id, unix_secs(*launched_at))),
+            TradeStateMachine::SwapdRunning(SwapdRunning { timeline, .. }) => {
+                Some(timeline.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the transactions swapd reported so far for a running swap
+    pub fn swap_txids(&self) -> Option<Vec<SwapTxid>> {
+        if let TradeStateMachine::SwapdRunning(SwapdRunning { txids, .. }) = self {
+            Some(txids.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the swap id and launch time of a swapd launched for this swap that did not say
+    /// hello yet
+    pub fn awaited_swapd(&self) -> Option<(SwapId, SystemTime)> {
+        match self {
+            TradeStateMachine::SwapdLaunched(SwapdLaunched {
+                swap_id,
+                swapd_up: false,
+                launched_at,
+                ..
+            })
+            | TradeStateMachine::RestoringSwapd(RestoringSwapd {
+                swap_id,
+                swapd_up: false,
+                launched_at,
+                ..
+            }) => Some((*swap_id, *launched_at)),
+            _ => None,
+        }
+    }
+
+    /// Relaunches the swapd of a running or restoring swap from its latest checkpoint after it
+    /// crashed, as a restore would. Returns the state machine awaiting the new swapd, none if no
+    /// swapd of this swap is expected to be running.
+    pub fn restart_swapd(
+        &self,
+        runtime: &mut Runtime,
+        endpoints: &mut Endpoints,
+    ) -> Result<Option<TradeStateMachine>, Error> {
+        match self {
+            TradeStateMachine::SwapdRunning(SwapdRunning {
+                public_offer,
+                swap_id,
+                arbitrating_syncer,
+                accordant_syncer,
+                local_trade_role,
+                launched_at,
+                nickname,
+                ..
+            }) => restore_swapd(
+                runtime,
+                endpoints,
+                public_offer.clone(),
+                *swap_id,
+                (arbitrating_syncer.clone(), accordant_syncer.clone()),
+                *local_trade_role,
+                *launched_at,
+                nickname.clone(),
+                false,
+            )
+            .map(Some),
+            // a restore requested by a client stays cancellable until swapd gets the checkpoint
+            TradeStateMachine::RestoringSwapd(RestoringSwapd {
+                public_offer,
+                swap_id,
+                arbitrating_syncer,
+                accordant_syncer,
+                local_trade_role,
+                launched_at,
+                nickname,
+                requested,
+                ..
+            }) => restore_swapd(
+                runtime,
+                endpoints,
+                public_offer.clone(),
+                *swap_id,
+                (arbitrating_syncer.clone(), accordant_syncer.clone()),
+                *local_trade_role,
+                *launched_at,
+                nickname.clone(),
+                *requested,
+            )
+            .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the swap id of a restore requested by a client and still in progress, and whether
+    /// it can still be cancelled, i.e. its swapd did not receive the checkpoint yet. A swapd
+    /// relaunched after crashing mid-swap cannot be cancelled.
+    pub fn pending_restore(&self) -> Option<(SwapId, bool)> {
+        match self {
+            TradeStateMachine::ValidatingCheckpoint(ValidatingCheckpoint { swap_id, .. }) => {
+                Some((*swap_id, true))
+            }
+            TradeStateMachine::RestoringSwapd(RestoringSwapd {
+                swap_id, requested, ..
+            }) => Some((*swap_id, *requested)),
+            _ => None,
+        }
+    }
+
+    /// Returns the client awaiting the validation of the checkpoint of a restore
+    pub fn restore_client(&self) -> Option<ServiceId> {
+        match self {
+            TradeStateMachine::ValidatingCheckpoint(ValidatingCheckpoint { client, .. }) => {
+                Some(client.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the syncers the swap relies on, including the ones still launching or syncing
     pub fn syncers(&self) -> Vec<ServiceId> {
         match self {
             TradeStateMachine::SwapdLaunched(SwapdLaunched {
-                arbitrating_syncer_up,
-                accordant_syncer_up,
+                arbitrating_syncer,
+                accordant_syncer,
                 ..
             }) => {
-                let mut syncers = vec![];
-                if let Some(syncer) = arbitrating_syncer_up {
-                    syncers.push(syncer.clone());
-                }
-                if let Some(syncer) = accordant_syncer_up {
-                    syncers.push(syncer.clone());
-                }
-                syncers
+                vec![arbitrating_syncer.clone(), accordant_syncer.clone()]
+            }
+            TradeStateMachine::RestoringSwapd(RestoringSwapd {
+                arbitrating_syncer,
+                accordant_syncer,
+                ..
+            }) => {
+                vec![arbitrating_syncer.clone(), accordant_syncer.clone()]
             }
             TradeStateMachine::SwapdRunning(SwapdRunning {
                 arbitrating_syncer,
@@ -266,6 +659,66 @@ impl TradeStateMachine {
         }
     }
 
+    /// Returns if the swap, or the take not launched as a swap yet, survives the termination of a
+    /// service along with why, none if it does not rely on the service. A lost connection is
+    /// re-established once swapd reports it, but swapd stalls without its syncers.
+    pub fn teardown_impact(&self, service: &ServiceId) -> Option<(bool, String)> {
+        if self.get_connection().as_ref() == Some(service) {
+            match self {
+                TradeStateMachine::TakeOffer(_) | TradeStateMachine::TakerCommit(_) => Some((
+                    false,
+                    "the take fails before its swap is launched".to_string(),
+                )),
+                TradeStateMachine::SwapdLaunched(SwapdLaunched {
+                    local_trade_role: TradeRole::Maker,
+                    ..
+                })
+                | TradeStateMachine::SwapdRunning(SwapdRunning {
+                    local_trade_role: TradeRole::Maker,
+                    ..
+                }) => Some((true, "the taker reconnects to the listener".to_string())),
+                _ => Some((
+                    true,
+                    "the maker is reconnected to, up to the maximum reconnect attempts".to_string(),
+                )),
+            }
+        } else if self.syncers().contains(service) {
+            Some((
+                false,
+                "its swapd is bound to the syncer and stalls without it".to_string(),
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn funds_locked(&self) -> bool {
+        matches!(
+            self,
+            TradeStateMachine::SwapdRunning(SwapdRunning {
+                funds_locked: true,
+                ..
+            })
+        )
+    }
+
+    /// Returns the blockchain the local swap role locked funds on, Bob locks the arbitrating
+    /// amount and Alice the accordant amount
+    pub fn locked_blockchain(&self) -> Option<Blockchain> {
+        match self {
+            TradeStateMachine::SwapdRunning(SwapdRunning {
+                public_offer,
+                local_trade_role,
+                funds_locked: true,
+                ..
+            }) => match public_offer.swap_role(local_trade_role) {
+                SwapRole::Bob => Some(Blockchain::Bitcoin),
+                SwapRole::Alice => Some(Blockchain::Monero),
+            },
+            _ => None,
+        }
+    }
+
     pub fn needs_funding_monero(&self) -> Option<MoneroFundingInfo> {
         match self {
             TradeStateMachine::SwapdRunning(SwapdRunning {
@@ -287,6 +740,138 @@ impl TradeStateMachine {
             _ => None,
         }
     }
+
+    /// Dumps the coordination state of a swap, for the states a swap id is known in. Only
+    /// service ids, offer and funding state are dumped, the local swap parameters are not.
+    pub fn state_dump(&self) -> Option<TradeStateDump> {
+        let state = self.to_string();
+        match self {
+            TradeStateMachine::SwapdLaunched(SwapdLaunched {
+                peerd,
+                public_offer,
+                swap_id,
+                arbitrating_syncer,
+                accordant_syncer,
+                arbitrating_syncer_up,
+                accordant_syncer_up,
+                swapd_up,
+                init_swap,
+                local_trade_role,
+                ..
+            }) => Some(TradeStateDump {
+                swap_id: *swap_id,
+                state,
+                public_offer: public_offer.clone(),
+                trade_role: Some(*local_trade_role),
+                connection: Some(peerd.clone()),
+                connected: None,
+                arbitrating_syncer: Some(arbitrating_syncer.clone()),
+                arbitrating_syncer_up: arbitrating_syncer_up.is_some(),
+                accordant_syncer: Some(accordant_syncer.clone()),
+                accordant_syncer_up: accordant_syncer_up.is_some(),
+                swapd_up: *swapd_up,
+                funding_address: init_swap.funding_address.clone(),
+                funding_info: None,
+                auto_funded: None,
+                funds_locked: false,
+                reconnect_attempts: None,
+            }),
+            TradeStateMachine::RestoringSwapd(RestoringSwapd {
+                public_offer,
+                swap_id,
+                arbitrating_syncer_up,
+                accordant_syncer_up,
+                swapd_up,
+                local_trade_role,
+                ..
+            }) => Some(TradeStateDump {
+                swap_id: *swap_id,
+                state,
+                public_offer: public_offer.clone(),
+                trade_role: Some(*local_trade_role),
+                connection: None,
+                connected: None,
+                arbitrating_syncer: arbitrating_syncer_up.clone(),
+                arbitrating_syncer_up: arbitrating_syncer_up.is_some(),
+                accordant_syncer: accordant_syncer_up.clone(),
+                accordant_syncer_up: accordant_syncer_up.is_some(),
+                swapd_up: *swapd_up,
+                funding_address: None,
+                funding_info: None,
+                auto_funded: None,
+                funds_locked: false,
+                reconnect_attempts: None,
+            }),
+            TradeStateMachine::SwapdRunning(SwapdRunning {
+                peerd,
+                public_offer,
+                arbitrating_syncer,
+                accordant_syncer,
+                swap_id,
+                connected,
+                funding_info,
+                auto_funded,
+                funds_locked,
+                local_trade_role,
+                reconnect_attempts,
+                ..
+            }) => Some(TradeStateDump {
+                swap_id: *swap_id,
+                state,
+                public_offer: public_offer.clone(),
+                trade_role: Some(*local_trade_role),
+                connection: Some(peerd.clone()),
+                connected: Some(*connected),
+                arbitrating_syncer: Some(arbitrating_syncer.clone()),
+                arbitrating_syncer_up: true,
+                accordant_syncer: Some(accordant_syncer.clone()),
+                accordant_syncer_up: true,
+                swapd_up: true,
+                funding_address: None,
+                // funding infos display the address and amount only, never the address keys
+                funding_info: funding_info.as_ref().map(|info| match info {
+                    FundingInfo::Bitcoin(info) => info.to_string(),
+                    FundingInfo::Monero(info) => info.to_string(),
+                }),
+                auto_funded: Some(*auto_funded),
+                funds_locked: *funds_locked,
+                reconnect_attempts: Some(*reconnect_attempts),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Seconds elapsed between the unix epoch and the time
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_secs(0))
+        .as_secs()
+}
+
+/// Lowest cancel or punish timelock accepted in an offer, in blocks
+const MIN_TIMELOCK: u32 = 1;
+/// Highest cancel or punish timelock accepted in an offer, in blocks, about two weeks
+const MAX_TIMELOCK: u32 = 2016;
+
+/// Checks the timelocks of an offer are within bounds and the punish timelock comes after the
+/// cancel timelock, returns the violated constraint otherwise
+fn validate_timelocks(cancel_timelock: u32, punish_timelock: u32) -> Result<(), String> {
+    for (name, timelock) in [("cancel", cancel_timelock), ("punish", punish_timelock)].iter() {
+        if *timelock < MIN_TIMELOCK || *timelock > MAX_TIMELOCK {
+            return Err(format!(
+                "The {} timelock must be between {} and {} blocks, got {}",
+                name, MIN_TIMELOCK, MAX_TIMELOCK, timelock
+            ));
+        }
+    }
+    if cancel_timelock >= punish_timelock {
+        return Err(format!(
+            "The punish timelock ({}) must be greater than the cancel timelock ({})",
+            punish_timelock, cancel_timelock
+        ));
+    }
+    Ok(())
 }
 
 fn attempt_transition_to_make_offer(
@@ -295,34 +880,105 @@ fn attempt_transition_to_make_offer(
 ) -> Result<Option<TradeStateMachine>, Error> {
     match event.request.clone() {
         Request::MakeOffer(ProtoPublicOffer {
-            offer,
+            mut offer,
             arbitrating_addr,
             accordant_addr,
             public_addr,
             bind_addr,
-            ..
+            explicit_fee_strategy,
+            syncer_servers,
+            expiry,
         }) => {
+            if let Err(err) = validate_timelocks(
+                offer.cancel_timelock.as_u32(),
+                offer.punish_timelock.as_u32(),
+            ) {
+                warn!("Rejecting offer: {}", err);
+                event.complete_ctl(Request::Failure(Failure {
+                    code: FailureCode::InvalidTimelock,
+                    info: err,
+                }))?;
+                return Ok(None);
+            }
+            if let Err(err) = validate_syncer_instance(runtime, offer.network, &syncer_servers) {
+                warn!("Rejecting offer: {}", err);
+                event.complete_ctl(Request::Failure(Failure {
+                    code: FailureCode::Unknown,
+                    info: err,
+                }))?;
+                return Ok(None);
+            }
+            match resolve_fee_strategy(
+                runtime,
+                offer.network,
+                &offer.fee_strategy,
+                explicit_fee_strategy,
+            ) {
+                Ok(fee_strategy) => offer.fee_strategy = fee_strategy,
+                Err(err) => {
+                    event.complete_ctl(Request::Failure(Failure {
+                        code: FailureCode::Unknown,
+                        info: err.to_string(),
+                    }))?;
+                    return Ok(None);
+                }
+            }
             let node_id = runtime.services_ready().and_then(|_| {
                 let (peer_secret_key, peer_public_key) = runtime.peer_keys_ready()?;
                 let node_id = NodeId::from(peer_public_key);
                 let address_bound = runtime.listens.iter().any(|a| a == &bind_addr);
                 if !address_bound {
+                    if let Some(port) = bind_addr
+                        .port()
+                        .filter(|port| !runtime.config.is_listen_port_allowed(*port))
+                    {
+                        return Err(Error::Farcaster(format!(
+                            "Cannot listen on {}, the port {} is not part of the allowed listen \
+                            ports of the configuration",
+                            bind_addr, port
+                        )));
+                    }
+                    // a listener bound to another address on the same port would make the new
+                    // bind fail, report the conflict instead of attempting it
+                    if let Some(conflicting_addr) = runtime
+                        .listens
+                        .iter()
+                        .find(|a| a.port() == bind_addr.port())
+                    {
+                        let served_offers = runtime
+                            .trade_state_machines
+                            .iter()
+                            .filter(|tsm| tsm.open_offer_bind_addr() == Some(*conflicting_addr))
+                            .count();
+                        return Err(Error::Farcaster(format!(
+                            "Cannot listen on {}, its port is already used by the listener on {} \
+                            serving {} open offer(s). Make the offer with the same bind address \
+                            to share this listener or use another port.",
+                            bind_addr, conflicting_addr, served_offers
+                        )));
+                    }
                     // if address not bound, bind first
                     info!(
                         "{} for incoming peer connections on {}",
                         "Starting listener".bright_blue_bold(),
                         bind_addr.bright_blue_bold()
                     );
-                    runtime
-                        .listen(NodeAddr::new(node_id, bind_addr), peer_secret_key)
-                        .and_then(|_| {
-                            runtime.listens.insert(bind_addr);
-                            Ok(())
-                        })?;
+                    let child =
+                        runtime.listen(NodeAddr::new(node_id, bind_addr), peer_secret_key)?;
+                    runtime.listener_processes.insert(bind_addr, child);
+                    runtime.listens.insert(bind_addr);
                 } else {
-                    // no need for the keys, because peerd already knows them
-                    let msg = format!("Already listening on {}", &bind_addr);
-                    debug!("{}", &msg);
+                    // no need for the keys, because peerd already knows them, the listener is
+                    // shared with the offers already bound to this address
+                    let served_offers = runtime
+                        .trade_state_machines
+                        .iter()
+                        .filter(|tsm| tsm.open_offer_bind_addr() == Some(bind_addr))
+                        .count();
+                    debug!(
+                        "Already listening on {}, sharing the listener with {} open offer(s)",
+                        &bind_addr, served_offers
+                    );
                 }
                 info!(
                     "Connection daemon {} for incoming peer connections on {}",
@@ -333,10 +989,7 @@ fn attempt_transition_to_make_offer(
             });
             match node_id {
                 Err(err) => {
-                    event.complete_ctl(Request::Failure(Failure {
-                        code: FailureCode::Unknown,
-                        info: err.to_string(),
-                    }))?;
+                    event.complete_ctl(Request::from(err))?;
                     Ok(None)
                 }
                 Ok(node_id) => {
@@ -359,13 +1012,22 @@ fn attempt_transition_to_make_offer(
                         offer_info: OfferInfo {
                             offer: public_offer.to_string(),
                             details: public_offer.clone(),
+                            ttl: expiry,
                         },
                     }))?;
                     runtime.public_offers.insert(public_offer.clone());
+                    runtime.notify_node_event(
+                        event.endpoints,
+                        NodeEvent::OfferMade(public_offer.clone()),
+                    );
                     Ok(Some(TradeStateMachine::MakeOffer(MakeOffer {
                         public_offer,
                         arb_addr: arbitrating_addr,
                         acc_addr: accordant_addr,
+                        bind_addr,
+                        syncer_servers,
+                        client: event.source,
+                        expires_at: expiry.map(|expiry| SystemTime::now() + expiry),
                     })))
                 }
             }
@@ -387,6 +1049,83 @@ fn attempt_transition_to_make_offer(
     }
 }
 
+/// Resolve the fee strategy of a new offer: an explicit user value takes precedence over the
+/// configured network default, which takes precedence over the hard-coded fallback.
+fn resolve_fee_strategy(
+    runtime: &Runtime,
+    network: Network,
+    requested: &FeeStrategy<SatPerVByte>,
+    explicit_fee_strategy: bool,
+) -> Result<FeeStrategy<SatPerVByte>, Error> {
+    let (fee_strategy, source) = if explicit_fee_strategy {
+        (requested.clone(), "command-line")
+    } else if let Some(configured) = runtime.config.get_default_fee_strategy(network) {
+        let fee_strategy = FeeStrategy::from_str(&configured).map_err(|err| {
+            Error::Farcaster(format!(
+                "Invalid default fee strategy \"{}\" configured for {}: {}",
+                configured, network, err
+            ))
+        })?;
+        (fee_strategy, "config")
+    } else {
+        let fee_strategy = FeeStrategy::from_str(FARCASTER_DEFAULT_FEE_STRATEGY)
+            .map_err(|err| Error::Farcaster(err.to_string()))?;
+        (fee_strategy, "fallback")
+    };
+    if let Some(reason) = unsupported_fee_strategy(&fee_strategy) {
+        return Err(Error::Farcaster(format!(
+            "Invalid {} fee strategy {}, {}",
+            source, fee_strategy, reason
+        )));
+    }
+    info!(
+        "Using {} fee strategy {} for the new offer",
+        source,
+        fee_strategy.bright_white_bold()
+    );
+    Ok(fee_strategy)
+}
+
+/// Returns why the node cannot evaluate the fee strategy into a fee for the arbitrating
+/// transactions, if it cannot. Fee strategies in other units than sat/vB already fail to decode.
+fn unsupported_fee_strategy(fee_strategy: &FeeStrategy<SatPerVByte>) -> Option<String> {
+    match fee_strategy {
+        FeeStrategy::Fixed(fee) if fee.as_sat() == 0 => {
+            Some(s!("the fee rate must be strictly positive"))
+        }
+        _ => None,
+    }
+}
+
+/// Checks the syncer instance a swap is pinned to, if any, is configured for the network and is
+/// not combined with overridden servers
+fn validate_syncer_instance(
+    runtime: &Runtime,
+    network: Network,
+    syncer_servers: &SyncerServersOverride,
+) -> Result<(), String> {
+    match syncer_servers {
+        SyncerServersOverride {
+            syncer_instance: Some(name),
+            electrum_server: None,
+            monero_daemon: None,
+            monero_rpc_wallet: None,
+        } => runtime
+            .config
+            .get_syncer_instance(network, name)
+            .map(|_| ())
+            .ok_or_else(|| format!("No syncer instance {} configured for {}", name, network)),
+        SyncerServersOverride {
+            syncer_instance: Some(name),
+            ..
+        } => Err(format!(
+            "A swap pinned to the syncer instance {} cannot override its servers",
+            name
+        )),
+        _ => Ok(()),
+    }
+}
+
 fn attempt_transition_to_take_offer(
     mut event: Event,
     runtime: &mut Runtime,
@@ -396,22 +1135,109 @@ fn attempt_transition_to_take_offer(
             public_offer,
             external_address,
             internal_address,
+            syncer_servers,
         }) => {
-            if runtime.trade_state_machines.iter().any(|tsm| {
-                if let Some(tsm_public_offer) = tsm.consumed_offer() {
-                    tsm_public_offer == public_offer
-                } else {
+            let existing_swap = runtime
+                .trade_state_machines
+                .iter()
+                .find(|tsm| tsm.consumed_offer().as_ref() == Some(&public_offer))
+                .map(|tsm| tsm.swap_id());
+            let reject = match (
+                existing_swap,
+                runtime.config.get_duplicate_take_offer_policy(),
+            ) {
+                (Some(swap_id), DuplicateTakeOfferPolicy::Existing) => {
+                    let message = match swap_id {
+                        Some(swap_id) => {
+                            format!(
+                                "Offer {} is already taken by swap {}",
+                                public_offer.id(),
+                                swap_id
+                            )
+                        }
+                        None => format!("Offer {} is already being taken", public_offer.id()),
+                    };
+                    info!("{}", message);
+                    event.complete_ctl(Request::TookOffer(TookOffer {
+                        offerid: public_offer.id(),
+                        message,
+                    }))?;
+                    return Ok(None);
+                }
+                (Some(_), DuplicateTakeOfferPolicy::Allow) => {
+                    warn!(
+                        "Offer {} is already taken, taking it again as a new swap",
+                        public_offer.id()
+                    );
                     false
                 }
-            }) || runtime.public_offers.contains(&public_offer)
+                (Some(_), DuplicateTakeOfferPolicy::Reject) => true,
+                (None, _) => runtime.public_offers.contains(&public_offer),
+            };
+            if reject {
+                let msg = format!(
+                    "{} already exists or was already taken, ignoring request",
+                    &public_offer.to_string()
+                );
+                warn!("{}", msg.err());
+                event.complete_ctl(Request::Failure(Failure {
+                    code: FailureCode::Unknown,
+                    info: msg,
+                }))?;
+                return Ok(None);
+            }
+            if let Err(err) =
+                validate_syncer_instance(runtime, public_offer.offer.network, &syncer_servers)
+            {
+                warn!("Cannot take offer {}: {}", public_offer.id(), err);
+                event.complete_ctl(Request::Failure(Failure {
+                    code: FailureCode::Unknown,
+                    info: err,
+                }))?;
+                return Ok(None);
+            }
+            if let Some(reason) = unsupported_fee_strategy(&public_offer.offer.fee_strategy) {
+                match runtime.config.get_unsupported_fee_strategy_policy() {
+                    UnsupportedFeeStrategyPolicy::Reject => {
+                        let msg = format!(
+                            "Cannot take offer {}, its fee strategy {} is unsupported: {}",
+                            public_offer.id(),
+                            public_offer.offer.fee_strategy,
+                            reason
+                        );
+                        warn!("{}", msg.err());
+                        event.complete_ctl(Request::Failure(Failure {
+                            code: FailureCode::UnsupportedFeeStrategy,
+                            info: msg,
+                        }))?;
+                        return Ok(None);
+                    }
+                    UnsupportedFeeStrategyPolicy::Warn => {
+                        warn!(
+                            "Taking offer {} despite its unsupported fee strategy {}: {}",
+                            public_offer.id(),
+                            public_offer.offer.fee_strategy,
+                            reason
+                        );
+                    }
+                }
+            }
+            let reputation = runtime.peer_reputation(&public_offer.node_id);
+            if runtime.config.get_take_offer_peer_policy() == TakeOfferPeerPolicy::Trusted
+                && !reputation.is_trusted()
             {
                 let msg = format!(
-                    "{} already exists or was already taken, ignoring request",
-                    &public_offer.to_string()
+                    "Cannot take offer {}, its maker {} is not trusted: {} successful, {} refunded \
+                     and {} punished swaps",
+                    public_offer.id(),
+                    public_offer.node_id,
+                    reputation.success,
+                    reputation.refund,
+                    reputation.punish
                 );
                 warn!("{}", msg.err());
                 event.complete_ctl(Request::Failure(Failure {
-                    code: FailureCode::Unknown,
+                    code: FailureCode::UntrustedPeer,
                     info: msg,
                 }))?;
                 return Ok(None);
@@ -441,13 +1267,13 @@ fn attempt_transition_to_take_offer(
                         "Already connected to remote peer {} through a listener spawned connection {}",
                         node_id, existing_peer
                     );
-                    Ok(existing_peer.clone())
+                    Ok((existing_peer.clone(), false))
                 } else if runtime.registered_services.contains(&ServiceId::Peer(peer)) {
                     warn!(
                         "Already connected to remote peer {}",
                         peer.bright_blue_italic()
                     );
-                    Ok(ServiceId::Peer(peer))
+                    Ok((ServiceId::Peer(peer), false))
                 } else {
                     debug!(
                         "{} to remote peer {}",
@@ -455,18 +1281,34 @@ fn attempt_transition_to_take_offer(
                         peer.bright_blue_italic()
                     );
                     runtime.connect_peer(&peer, peer_secret_key)?;
-                    Ok(ServiceId::Peer(peer))
+                    Ok((ServiceId::Peer(peer), true))
                 }
             });
             match res {
                 Err(err) => {
-                    event.complete_ctl(Request::Failure(Failure {
-                        code: FailureCode::Unknown,
-                        info: err.to_string(),
-                    }))?;
+                    event.complete_ctl(Request::from(err))?;
                     Ok(None)
                 }
-                Ok(peer_service_id) => {
+                Ok((peer_service_id, new_connection)) => {
+                    // a new connection did not negotiate its version yet, it is checked once the
+                    // swap launches
+                    if let Some(version) = Some(&peer_service_id)
+                        .filter(|_| !new_connection)
+                        .and_then(|peerd| runtime.incompatible_protocol_version(peerd))
+                    {
+                        let msg = format!(
+                            "Cannot take offer {}, the protocol version {} negotiated with {} does not support swaps",
+                            public_offer.id(),
+                            version,
+                            peer_service_id
+                        );
+                        warn!("{}", msg.err());
+                        event.complete_ctl(Request::Failure(Failure {
+                            code: FailureCode::IncompatibleProtocolVersion,
+                            info: msg,
+                        }))?;
+                        return Ok(None);
+                    }
                     let offer_registered = "Public offer registered".to_string();
                     info!(
                         "{}: {:#}",
@@ -480,6 +1322,7 @@ fn attempt_transition_to_take_offer(
                             public_offer: public_offer.clone(),
                             external_address: external_address.clone(),
                             internal_address,
+                            syncer_servers: syncer_servers.clone(),
                         }),
                     )?;
                     event.complete_ctl(Request::TookOffer(TookOffer {
@@ -492,6 +1335,8 @@ fn attempt_transition_to_take_offer(
                         arb_addr: external_address,
                         acc_addr: internal_address,
                         peerd: peer_service_id,
+                        syncer_servers,
+                        client: event.source,
                     })))
                 }
             }
@@ -513,7 +1358,7 @@ fn attempt_transition_to_take_offer(
     }
 }
 
-fn attempt_transition_to_restoring_swapd(
+fn attempt_transition_to_validating_checkpoint(
     mut event: Event,
     runtime: &mut Runtime,
 ) -> Result<Option<TradeStateMachine>, Error> {
@@ -543,6 +1388,17 @@ fn attempt_transition_to_restoring_swapd(
                 return Ok(None);
             }
 
+            // check if the swap is not already being restored
+            if runtime.trade_state_machines.iter().any(|tsm| {
+                tsm.validating_checkpoint() == Some(swap_id) || tsm.swap_id() == Some(swap_id)
+            }) {
+                event.complete_ctl(Request::Failure(Failure {
+                    code: FailureCode::Unknown,
+                    info: "A restore of this swap is already in progress.".to_string(),
+                }))?;
+                return Ok(None);
+            }
+
             let CheckpointEntry {
                 public_offer,
                 trade_role,
@@ -552,7 +1408,7 @@ fn attempt_transition_to_restoring_swapd(
                 .iter()
                 .find(|entry| entry.swap_id == swap_id)
             {
-                Some(ce) => ce,
+                Some(ce) => ce.clone(),
                 None => {
                     event.complete_ctl(Request::Failure(Failure {
                         code: FailureCode::Unknown,
@@ -562,39 +1418,19 @@ fn attempt_transition_to_restoring_swapd(
                     return Ok(None);
                 }
             };
-            let arbitrating_syncer_up = syncer_up(
-                &mut runtime.spawning_services,
-                &mut runtime.registered_services,
-                Blockchain::Bitcoin,
-                public_offer.offer.network,
-                &runtime.config,
-            )?;
-            let accordant_syncer_up = syncer_up(
-                &mut runtime.spawning_services,
-                &mut runtime.registered_services,
-                Blockchain::Monero,
-                public_offer.offer.network,
-                &runtime.config,
-            )?;
-
-            let _child = launch(
-                "swapd",
-                &[
-                    swap_id.to_hex(),
-                    public_offer.to_string(),
-                    trade_role.to_string(),
-                ],
-            )?;
 
-            event.complete_ctl(Request::String("Restoring checkpoint.".to_string()))?;
+            let client = event.source.clone();
+            event
+                .complete_ctl_service(ServiceId::Database, Request::ValidateCheckpoint(swap_id))?;
 
-            Ok(Some(TradeStateMachine::RestoringSwapd(RestoringSwapd {
-                public_offer: public_offer.clone(),
-                swap_id,
-                arbitrating_syncer_up,
-                accordant_syncer_up,
-                swapd_up: false,
-            })))
+            Ok(Some(TradeStateMachine::ValidatingCheckpoint(
+                ValidatingCheckpoint {
+                    public_offer,
+                    trade_role,
+                    swap_id,
+                    client,
+                },
+            )))
         }
         req => {
             if let Request::Hello = req {
@@ -613,15 +1449,199 @@ fn attempt_transition_to_restoring_swapd(
     }
 }
 
+fn attempt_transition_from_validating_checkpoint_to_restoring_swapd(
+    event: Event,
+    runtime: &mut Runtime,
+    validating_checkpoint: ValidatingCheckpoint,
+) -> Result<Option<TradeStateMachine>, Error> {
+    let ValidatingCheckpoint {
+        public_offer,
+        trade_role,
+        swap_id,
+        client,
+    } = validating_checkpoint;
+    match (event.request.clone(), event.source.clone()) {
+        (Request::CheckpointValidation(validation), ServiceId::Database) => {
+            let reason = match validation {
+                CheckpointValidation::Valid(entry)
+                    if entry.public_offer == public_offer && entry.trade_role == trade_role =>
+                {
+                    None
+                }
+                CheckpointValidation::Valid(_) => Some(
+                    "Checkpoint does not reference the known public offer of the swap.".to_string(),
+                ),
+                CheckpointValidation::Corrupt(_, reason) => Some(reason),
+            };
+            if let Some(reason) = reason {
+                warn!(
+                    "{} | Aborting restore, checkpoint is corrupt: {}",
+                    swap_id.bright_blue_italic(),
+                    reason
+                );
+                event.complete_ctl_service(
+                    client,
+                    Request::Failure(Failure {
+                        code: FailureCode::CheckpointCorrupt,
+                        info: format!("Checkpoint is corrupt, aborting restore: {}", reason),
+                    }),
+                )?;
+                return Ok(None);
+            }
+
+            let network = public_offer.offer.network;
+            let restoring_swapd = restore_swapd(
+                runtime,
+                event.endpoints,
+                public_offer,
+                swap_id,
+                (
+                    ServiceId::Syncer(Blockchain::Bitcoin, network),
+                    ServiceId::Syncer(Blockchain::Monero, network),
+                ),
+                trade_role,
+                SystemTime::now(),
+                None,
+                true,
+            )?;
+
+            event.complete_ctl_service(
+                client,
+                Request::String("Restoring checkpoint.".to_string()),
+            )?;
+
+            Ok(Some(restoring_swapd))
+        }
+        _ => Ok(Some(TradeStateMachine::ValidatingCheckpoint(
+            ValidatingCheckpoint {
+                public_offer,
+                trade_role,
+                swap_id,
+                client,
+            },
+        ))),
+    }
+}
+
+/// Launches the swapd restoring the swap from its latest checkpoint, along with the given
+/// arbitrating and accordant syncers, and returns the state machine awaiting them. A swapd
+/// relaunched after crashing keeps the syncers its swap was pinned to.
+#[allow(clippy::too_many_arguments)]
+fn restore_swapd(
+    runtime: &mut Runtime,
+    endpoints: &mut Endpoints,
+    public_offer: PublicOffer,
+    swap_id: SwapId,
+    (arbitrating_syncer, accordant_syncer): (ServiceId, ServiceId),
+    local_trade_role: TradeRole,
+    launched_at: SystemTime,
+    nickname: Option<String>,
+    requested: bool,
+) -> Result<TradeStateMachine, Error> {
+    let network = public_offer.offer.network;
+    let arbitrating_syncer_up = pinned_syncer_up(
+        runtime,
+        endpoints,
+        Blockchain::Bitcoin,
+        network,
+        &arbitrating_syncer,
+        swap_id,
+    )?;
+    let accordant_syncer_up = pinned_syncer_up(
+        runtime,
+        endpoints,
+        Blockchain::Monero,
+        network,
+        &accordant_syncer,
+        swap_id,
+    )?;
+
+    let child = launch_swapd(
+        local_trade_role,
+        public_offer.clone(),
+        swap_id,
+        &arbitrating_syncer,
+        &accordant_syncer,
+        &runtime.config.get_funding_progress_confirmations(),
+        runtime.config.is_multi_tenant(),
+    )?;
+    runtime.swapd_processes.insert(swap_id, child);
+    runtime
+        .spawning_services
+        .insert(ServiceId::Swap(swap_id), SystemTime::now());
+    // the checkpoint does not tell farcasterd whether the funds are locked, the restored swap is
+    // assumed locked but left out of the value locked until a lock is observed
+    runtime.unconfirmed_locks.insert(swap_id);
+
+    Ok(TradeStateMachine::RestoringSwapd(RestoringSwapd {
+        public_offer,
+        swap_id,
+        arbitrating_syncer,
+        accordant_syncer,
+        arbitrating_syncer_up,
+        accordant_syncer_up,
+        swapd_up: false,
+        local_trade_role,
+        launched_at,
+        nickname,
+        requested,
+    }))
+}
+
+/// Brings up a given syncer and returns it if it is ready and synced. A custom syncer is only
+/// identified by a hash of its servers, it cannot be relaunched once it exited.
+fn pinned_syncer_up(
+    runtime: &mut Runtime,
+    endpoints: &mut Endpoints,
+    blockchain: Blockchain,
+    network: Network,
+    syncer: &ServiceId,
+    swap_id: SwapId,
+) -> Result<Option<ServiceId>, Error> {
+    let syncer_servers = match syncer {
+        ServiceId::NamedSyncer(_, _, name) => SyncerServersOverride {
+            syncer_instance: Some(name.clone()),
+            ..none!()
+        },
+        ServiceId::CustomSyncer(..) => {
+            if runtime.registered_services.contains(syncer) {
+                return Ok(runtime.synced_syncer(endpoints, syncer.clone(), swap_id));
+            }
+            if runtime.spawning_services.contains_key(syncer) {
+                return Ok(None);
+            }
+            return Err(Error::Farcaster(format!(
+                "{} exited, the servers it was launched with are unknown",
+                syncer
+            )));
+        }
+        _ => none!(),
+    };
+    Ok(syncer_up(
+        &mut runtime.spawning_services,
+        &mut runtime.registered_services,
+        blockchain,
+        network,
+        &runtime.config,
+        &syncer_servers,
+    )?
+    .ready()?
+    .and_then(|syncer| runtime.synced_syncer(endpoints, syncer, swap_id)))
+}
+
 fn attempt_transition_to_taker_committed(
     mut event: Event,
-    _runtime: &mut Runtime,
+    runtime: &mut Runtime,
     make_offer: MakeOffer,
 ) -> Result<Option<TradeStateMachine>, Error> {
     let MakeOffer {
         public_offer,
         arb_addr,
         acc_addr,
+        bind_addr,
+        syncer_servers,
+        client,
+        expires_at,
     } = make_offer;
     match (event.request.clone(), event.source.clone()) {
         (
@@ -633,6 +1653,70 @@ fn attempt_transition_to_taker_committed(
             ServiceId::Peer(..),
         ) => {
             if public_offer == committed_public_offer {
+                if let Some(version) = runtime.incompatible_protocol_version(&event.source) {
+                    let msg = format!(
+                        "Refused the commitment of swap {} to offer {}, the protocol version {} negotiated with {} does not support swaps",
+                        swap_id,
+                        public_offer.id(),
+                        version,
+                        event.source
+                    );
+                    error!("{}", msg.err());
+                    runtime.abort_taker_commit(
+                        event.endpoints,
+                        event.source.clone(),
+                        swap_id,
+                        Failure {
+                            code: FailureCode::IncompatibleProtocolVersion,
+                            info: msg,
+                        },
+                    );
+                    return Ok(Some(TradeStateMachine::MakeOffer(MakeOffer {
+                        public_offer,
+                        arb_addr,
+                        acc_addr,
+                        bind_addr,
+                        syncer_servers,
+                        client,
+                        expires_at,
+                    })));
+                }
+                let margin = runtime.config.get_offer_expiry_margin();
+                if let Some(remaining) = expires_at
+                    .filter(|_| !margin.is_zero())
+                    .map(|expires_at| {
+                        expires_at
+                            .duration_since(SystemTime::now())
+                            .unwrap_or_else(|_| Duration::from_secs(0))
+                    })
+                    .filter(|remaining| *remaining < margin)
+                {
+                    let msg = format!(
+                        "Rejected the commitment of swap {} to offer {}, the offer expires in {}s",
+                        swap_id,
+                        public_offer.id(),
+                        remaining.as_secs()
+                    );
+                    warn!("{}", msg.err());
+                    runtime.abort_taker_commit(
+                        event.endpoints,
+                        event.source.clone(),
+                        swap_id,
+                        Failure {
+                            code: FailureCode::OfferExpiringSoon,
+                            info: msg,
+                        },
+                    );
+                    return Ok(Some(TradeStateMachine::MakeOffer(MakeOffer {
+                        public_offer,
+                        arb_addr,
+                        acc_addr,
+                        bind_addr,
+                        syncer_servers,
+                        client,
+                        expires_at,
+                    })));
+                }
                 let source = event.source.clone();
                 let btc_addr_req = Request::BitcoinAddress(BitcoinAddress(swap_id, arb_addr));
                 event.send_msg_service(ServiceId::Wallet, btc_addr_req)?;
@@ -650,6 +1734,8 @@ fn attempt_transition_to_taker_committed(
                 Ok(Some(TradeStateMachine::TakerCommit(TakerCommit {
                     peerd: source,
                     public_offer,
+                    syncer_servers,
+                    client,
                 })))
             } else {
                 error!(
@@ -660,14 +1746,43 @@ fn attempt_transition_to_taker_committed(
                     public_offer,
                     arb_addr,
                     acc_addr,
+                    bind_addr,
+                    syncer_servers,
+                    client,
+                    expires_at,
                 })))
             }
         }
-        (Request::RevokeOffer(revoke_public_offer), _) => {
+        (
+            Request::RevokeOffer(RevokeOffer {
+                public_offer: revoke_public_offer,
+                ..
+            }),
+            source,
+        ) => {
             debug!("attempting to revoke {}", public_offer);
             if revoke_public_offer == public_offer {
-                info!("Revoked offer {}", public_offer);
-                event.complete_ctl(Request::String("Successfully revoked offer.".to_string()))?;
+                runtime.notify_node_event(
+                    event.endpoints,
+                    NodeEvent::OfferRevoked(public_offer.clone()),
+                );
+                // farcasterd revokes the offers once expired, the client that made it is told
+                if source == ServiceId::Farcasterd {
+                    info!("Revoked expired offer {}", public_offer);
+                    if let Err(err) = event.complete_ctl_service(
+                        client,
+                        Request::String(format!(
+                            "Offer {} expired and was revoked.",
+                            public_offer.id()
+                        )),
+                    ) {
+                        debug!("Failed to notify the client of the offer expiry: {}", err);
+                    }
+                } else {
+                    info!("Revoked offer {}", public_offer);
+                    event
+                        .complete_ctl(Request::String("Successfully revoked offer.".to_string()))?;
+                }
                 Ok(None)
             } else {
                 let msg = "Cannot revoke offer, it does not exist".to_string();
@@ -677,6 +1792,10 @@ fn attempt_transition_to_taker_committed(
                     public_offer,
                     arb_addr,
                     acc_addr,
+                    bind_addr,
+                    syncer_servers,
+                    client,
+                    expires_at,
                 })))
             }
         }
@@ -697,6 +1816,10 @@ fn attempt_transition_to_taker_committed(
                 public_offer,
                 arb_addr,
                 acc_addr,
+                bind_addr,
+                syncer_servers,
+                client,
+                expires_at,
             })))
         }
     }
@@ -710,10 +1833,20 @@ fn attempt_transition_from_taker_commit_to_swapd_launched(
     let TakerCommit {
         peerd,
         public_offer,
+        syncer_servers,
+        client,
     } = taker_commit;
     match event.request {
         Request::LaunchSwap(launch_swap) => {
-            let tsm = transition_to_swapd_launched_tsm(runtime, launch_swap, peerd, public_offer)?;
+            let tsm = transition_to_swapd_launched_tsm(
+                runtime,
+                event.endpoints,
+                launch_swap,
+                peerd,
+                public_offer,
+                syncer_servers,
+                client,
+            )?;
             Ok(Some(tsm))
         }
         req => {
@@ -725,13 +1858,15 @@ fn attempt_transition_from_taker_commit_to_swapd_launched(
             Ok(Some(TradeStateMachine::TakerCommit(TakerCommit {
                 peerd,
                 public_offer,
+                syncer_servers,
+                client,
             })))
         }
     }
 }
 
 fn attempt_transition_from_take_offer_to_swapd_launched(
-    event: Event,
+    mut event: Event,
     runtime: &mut Runtime,
     take_offer: TakeOffer,
 ) -> Result<Option<TradeStateMachine>, Error> {
@@ -740,10 +1875,35 @@ fn attempt_transition_from_take_offer_to_swapd_launched(
         arb_addr,
         acc_addr,
         peerd,
+        syncer_servers,
+        client,
     } = take_offer;
-    match event.request {
+    match event.request.clone() {
         Request::LaunchSwap(launch_swap) => {
-            let tsm = transition_to_swapd_launched_tsm(runtime, launch_swap, peerd, public_offer)?;
+            if let Some(version) = runtime.incompatible_protocol_version(&peerd) {
+                let msg = format!(
+                    "Refusing to launch swap {}, the protocol version {} negotiated with {} does not support swaps",
+                    launch_swap.swap_id, version, peerd
+                );
+                error!("{}", msg.err());
+                event.send_ctl_service(
+                    client,
+                    Request::Failure(Failure {
+                        code: FailureCode::IncompatibleProtocolVersion,
+                        info: msg,
+                    }),
+                )?;
+                return Ok(None);
+            }
+            let tsm = transition_to_swapd_launched_tsm(
+                runtime,
+                event.endpoints,
+                launch_swap,
+                peerd,
+                public_offer,
+                syncer_servers,
+                client,
+            )?;
             Ok(Some(tsm))
         }
         req => {
@@ -757,6 +1917,8 @@ fn attempt_transition_from_take_offer_to_swapd_launched(
                 arb_addr,
                 acc_addr,
                 peerd,
+                syncer_servers,
+                client,
             })))
         }
     }
@@ -764,9 +1926,12 @@ fn attempt_transition_from_take_offer_to_swapd_launched(
 
 fn transition_to_swapd_launched_tsm(
     runtime: &mut Runtime,
+    endpoints: &mut Endpoints,
     launch_swap: LaunchSwap,
     peerd: ServiceId,
     public_offer: PublicOffer,
+    syncer_servers: SyncerServersOverride,
+    client: ServiceId,
 ) -> Result<TradeStateMachine, Error> {
     let LaunchSwap {
         remote_commit,
@@ -777,32 +1942,84 @@ fn transition_to_swapd_launched_tsm(
         ..
     } = launch_swap;
     let network = public_offer.offer.network;
+    // a swap overriding the syncer servers is served by a custom syncer, see syncer_service_id
+    let (arbitrating_syncer, _) = syncer_service_id(
+        &runtime.config,
+        Blockchain::Bitcoin,
+        network,
+        &syncer_servers,
+    )?;
+    let (accordant_syncer, _) = syncer_service_id(
+        &runtime.config,
+        Blockchain::Monero,
+        network,
+        &syncer_servers,
+    )?;
     let arbitrating_syncer_up = syncer_up(
         &mut runtime.spawning_services,
         &mut runtime.registered_services,
         Blockchain::Bitcoin,
         network,
         &runtime.config,
-    )?;
+        &syncer_servers,
+    )?
+    .ready()?
+    .and_then(|syncer| runtime.synced_syncer(endpoints, syncer, swap_id));
     let accordant_syncer_up = syncer_up(
         &mut runtime.spawning_services,
         &mut runtime.registered_services,
         Blockchain::Monero,
         network,
         &runtime.config,
-    )?;
+        &syncer_servers,
+    )?
+    .ready()?
+    .and_then(|syncer| runtime.synced_syncer(endpoints, syncer, swap_id));
     trace!(
         "launching swapd with swap_id: {}",
         swap_id.bright_yellow_bold()
     );
 
     runtime.stats.incr_initiated();
-    launch_swapd(local_trade_role, public_offer.clone(), swap_id)?;
+    let child = launch_swapd(
+        local_trade_role,
+        public_offer.clone(),
+        swap_id,
+        &arbitrating_syncer,
+        &accordant_syncer,
+        &runtime.config.get_funding_progress_confirmations(),
+        runtime.config.is_multi_tenant(),
+    )?;
+    runtime.swapd_processes.insert(swap_id, child);
+    runtime
+        .spawning_services
+        .insert(ServiceId::Swap(swap_id), SystemTime::now());
+
+    // the client that made or took the offer may be long gone, e.g. a cli that exited after
+    // registering the offer, so the notification is best effort
+    if let Err(err) = send_traced(
+        endpoints,
+        ServiceBus::Ctl,
+        runtime.identity(),
+        client.clone(),
+        Request::SwapStarted(SwapStarted {
+            offer_id: public_offer.id(),
+            swap_id,
+        }),
+    ) {
+        debug!(
+            "Could not notify {} of swap {} start: {}",
+            client, swap_id, err
+        );
+    }
+    runtime.notify_node_event(endpoints, NodeEvent::SwapStarted(swap_id));
 
     Ok(TradeStateMachine::SwapdLaunched(SwapdLaunched {
         peerd: peerd.clone(),
         swap_id,
         public_offer: public_offer.clone(),
+        arbitrating_syncer,
+        accordant_syncer,
         arbitrating_syncer_up,
         accordant_syncer_up,
         swapd_up: false,
@@ -815,35 +2032,48 @@ fn transition_to_swapd_launched_tsm(
             funding_address,
         },
         local_trade_role,
+        launched_at: SystemTime::now(),
+        nickname: None,
     }))
 }
 
 fn attempt_transition_from_swapd_launched_to_swapd_running(
     event: Event,
-    _runtime: &mut Runtime,
+    runtime: &mut Runtime,
     swapd_launched: SwapdLaunched,
 ) -> Result<Option<TradeStateMachine>, Error> {
     let SwapdLaunched {
         peerd,
         public_offer,
         swap_id,
+        arbitrating_syncer: expected_arbitrating_syncer,
+        accordant_syncer: expected_accordant_syncer,
         mut arbitrating_syncer_up,
         mut accordant_syncer_up,
         mut swapd_up,
         init_swap,
         local_trade_role,
+        launched_at,
+        nickname,
     } = swapd_launched;
     match (event.request.clone(), event.source.clone()) {
-        (Request::Hello, source)
-            if ServiceId::Syncer(Blockchain::Monero, public_offer.offer.network) == source =>
-        {
-            accordant_syncer_up = Some(source);
+        (Request::Hello, source) if expected_accordant_syncer == source => {
+            accordant_syncer_up = runtime.synced_syncer(event.endpoints, source, swap_id);
         }
         (Request::Hello, source) if ServiceId::Swap(swap_id) == source => {
             swapd_up = true;
         }
-        (Request::Hello, source)
-            if ServiceId::Syncer(Blockchain::Bitcoin, public_offer.offer.network) == source =>
+        (Request::Hello, source) if expected_arbitrating_syncer == source => {
+            arbitrating_syncer_up = runtime.synced_syncer(event.endpoints, source, swap_id);
+        }
+        // the first height reported by the syncer, it is now synced
+        (Request::SyncerEvent(SyncerEvent::HeightChanged(..)), source)
+            if expected_accordant_syncer == source =>
+        {
+            accordant_syncer_up = Some(source);
+        }
+        (Request::SyncerEvent(SyncerEvent::HeightChanged(..)), source)
+            if expected_arbitrating_syncer == source =>
         {
             arbitrating_syncer_up = Some(source);
         }
@@ -877,17 +2107,28 @@ fn attempt_transition_from_swapd_launched_to_swapd_running(
             connected: true,
             funding_info: None,
             auto_funded: false,
+            funds_locked: false,
+            local_trade_role,
+            reconnect_attempts: 0,
+            txids: none!(),
+            timeline: SwapTimeline::new(swap_id, unix_secs(launched_at)),
+            launched_at,
+            nickname,
         })))
     } else {
         Ok(Some(TradeStateMachine::SwapdLaunched(SwapdLaunched {
             swap_id,
             public_offer,
             peerd,
+            arbitrating_syncer: expected_arbitrating_syncer,
+            accordant_syncer: expected_accordant_syncer,
             arbitrating_syncer_up,
             accordant_syncer_up,
             swapd_up,
             init_swap,
             local_trade_role,
+            launched_at,
+            nickname,
         })))
     }
 }
@@ -900,21 +2141,34 @@ fn attempt_transition_from_restoring_swapd_to_swapd_running(
     let RestoringSwapd {
         public_offer,
         swap_id,
+        arbitrating_syncer: expected_arbitrating_syncer,
+        accordant_syncer: expected_accordant_syncer,
         mut arbitrating_syncer_up,
         mut accordant_syncer_up,
         mut swapd_up,
+        local_trade_role,
+        launched_at,
+        nickname,
+        requested,
     } = restoring_swapd;
     match (event.request.clone(), event.source.clone()) {
-        (Request::Hello, source)
-            if ServiceId::Syncer(Blockchain::Monero, public_offer.offer.network) == source =>
-        {
-            accordant_syncer_up = Some(source);
+        (Request::Hello, source) if expected_accordant_syncer == source => {
+            accordant_syncer_up = runtime.synced_syncer(event.endpoints, source, swap_id);
         }
         (Request::Hello, source) if ServiceId::Swap(swap_id) == source => {
             swapd_up = true;
         }
-        (Request::Hello, source)
-            if ServiceId::Syncer(Blockchain::Bitcoin, public_offer.offer.network) == source =>
+        (Request::Hello, source) if expected_arbitrating_syncer == source => {
+            arbitrating_syncer_up = runtime.synced_syncer(event.endpoints, source, swap_id);
+        }
+        // the first height reported by the syncer, it is now synced
+        (Request::SyncerEvent(SyncerEvent::HeightChanged(..)), source)
+            if expected_accordant_syncer == source =>
+        {
+            accordant_syncer_up = Some(source);
+        }
+        (Request::SyncerEvent(SyncerEvent::HeightChanged(..)), source)
+            if expected_arbitrating_syncer == source =>
         {
             arbitrating_syncer_up = Some(source);
         }
@@ -927,6 +2181,9 @@ fn attempt_transition_from_restoring_swapd_to_swapd_running(
     ) {
         info!("Restoring swap {}", swap_id.bright_blue_italic());
         runtime.stats.incr_initiated();
+        if requested {
+            runtime.restored_swaps.insert(swap_id);
+        }
         event.complete_ctl_service(ServiceId::Database, Request::RestoreCheckpoint(swap_id))?;
 
         Ok(Some(TradeStateMachine::SwapdRunning(SwapdRunning {
@@ -938,18 +2195,47 @@ fn attempt_transition_from_restoring_swapd_to_swapd_running(
             connected: true,
             auto_funded: false,
             funding_info: None,
+            // a restored swap may have locked its funds before being checkpointed
+            funds_locked: true,
+            local_trade_role,
+            reconnect_attempts: 0,
+            txids: none!(),
+            timeline: SwapTimeline::new(swap_id, unix_secs(launched_at)),
+            launched_at,
+            nickname,
         })))
     } else {
         Ok(Some(TradeStateMachine::RestoringSwapd(RestoringSwapd {
             public_offer,
             swap_id,
+            arbitrating_syncer: expected_arbitrating_syncer,
+            accordant_syncer: expected_accordant_syncer,
             arbitrating_syncer_up,
             accordant_syncer_up,
             swapd_up,
+            local_trade_role,
+            launched_at,
+            nickname,
+            requested,
         })))
     }
 }
 
+/// Returns the node id of the counterparty, the maker of the offer for a taker and the remote
+/// node of the connection for a maker. A restored maker swap does not know the node id of its
+/// taker.
+fn counterparty_node_id(
+    local_trade_role: TradeRole,
+    public_offer: &PublicOffer,
+    peerd: &ServiceId,
+) -> Option<PublicKey> {
+    match (local_trade_role, peerd) {
+        (TradeRole::Taker, _) => Some(public_offer.node_id),
+        (TradeRole::Maker, ServiceId::Peer(node_addr)) => Some(node_addr.id.public_key()),
+        (TradeRole::Maker, _) => None,
+    }
+}
+
 fn attempt_transition_to_end(
     mut event: Event,
     runtime: &mut Runtime,
@@ -964,6 +2250,13 @@ fn attempt_transition_to_end(
         accordant_syncer,
         funding_info,
         auto_funded,
+        funds_locked,
+        local_trade_role,
+        reconnect_attempts,
+        mut txids,
+        mut timeline,
+        launched_at,
+        nickname,
     } = swapd_running;
     match (event.request.clone(), event.source.clone()) {
         (Request::Hello, source) if source == peerd => {
@@ -979,6 +2272,72 @@ fn attempt_transition_to_end(
                 accordant_syncer,
                 funding_info,
                 auto_funded,
+                funds_locked,
+                local_trade_role,
+                reconnect_attempts: 0,
+                txids,
+                timeline,
+                launched_at,
+                nickname,
+            })))
+        }
+
+        (Request::SwapTxid(swap_txid), source) if ServiceId::Swap(swap_id) == source => {
+            debug!(
+                "{} | {} transaction {}",
+                swap_id.bright_blue_italic(),
+                swap_txid.tx_label.bright_white_bold(),
+                swap_txid.txid.bright_yellow_italic()
+            );
+            if swap_txid.tx_label == TxLabel::Lock {
+                timeline
+                    .locked
+                    .get_or_insert_with(|| unix_secs(SystemTime::now()));
+                // the arbitrating lock holds the funds of Bob
+                if public_offer.swap_role(&local_trade_role) == SwapRole::Bob {
+                    runtime.unconfirmed_locks.remove(&swap_id);
+                }
+            }
+            txids.retain(|entry| entry.tx_label != swap_txid.tx_label);
+            txids.push(swap_txid);
+            Ok(Some(TradeStateMachine::SwapdRunning(SwapdRunning {
+                peerd,
+                public_offer,
+                swap_id,
+                connected,
+                arbitrating_syncer,
+                accordant_syncer,
+                funding_info,
+                auto_funded,
+                funds_locked,
+                local_trade_role,
+                reconnect_attempts,
+                txids,
+                timeline,
+                launched_at,
+                nickname,
+            })))
+        }
+
+        (Request::FundingInfo(info), _)
+            if runtime.funding_address_refused(event.endpoints, swap_id, &info) =>
+        {
+            Ok(Some(TradeStateMachine::SwapdRunning(SwapdRunning {
+                peerd,
+                public_offer,
+                swap_id,
+                connected,
+                arbitrating_syncer,
+                accordant_syncer,
+                funding_info,
+                auto_funded,
+                funds_locked,
+                local_trade_role,
+                reconnect_attempts,
+                txids,
+                timeline,
+                launched_at,
+                nickname,
             })))
         }
 
@@ -990,7 +2349,7 @@ fn attempt_transition_to_end(
             }) => {
                 runtime.stats.incr_awaiting_funding(&Blockchain::Bitcoin);
                 let network = address.network.into();
-                if let Some(auto_fund_config) = runtime.config.get_auto_funding_config(network) {
+                if let Some(auto_fund_config) = runtime.auto_funding_config(network) {
                     info!(
                         "{} | Attempting to auto-fund Bitcoin",
                         swap_id.bright_blue_italic()
@@ -1048,6 +2407,13 @@ fn attempt_transition_to_end(
                                 accordant_syncer,
                                 funding_info: Some(info),
                                 auto_funded: true,
+                                funds_locked,
+                                local_trade_role,
+                                reconnect_attempts,
+                                txids,
+                                timeline,
+                                launched_at,
+                                nickname,
                             })))
                         }
                         Err(err) => {
@@ -1065,6 +2431,13 @@ fn attempt_transition_to_end(
                                 accordant_syncer,
                                 funding_info: Some(info),
                                 auto_funded: false,
+                                funds_locked,
+                                local_trade_role,
+                                reconnect_attempts,
+                                txids,
+                                timeline,
+                                launched_at,
+                                nickname,
                             })))
                         }
                     }
@@ -1078,6 +2451,13 @@ fn attempt_transition_to_end(
                         accordant_syncer,
                         funding_info: Some(info.clone()),
                         auto_funded: false,
+                        funds_locked,
+                        local_trade_role,
+                        reconnect_attempts,
+                        txids,
+                        timeline,
+                        launched_at,
+                        nickname,
                     })))
                 }
             }
@@ -1088,7 +2468,7 @@ fn attempt_transition_to_end(
             }) => {
                 runtime.stats.incr_awaiting_funding(&Blockchain::Monero);
                 let network = address.network.into();
-                if let Some(auto_fund_config) = runtime.config.get_auto_funding_config(network) {
+                if let Some(auto_fund_config) = runtime.auto_funding_config(network) {
                     info!(
                         "{} | Attempting to auto-fund Monero",
                         swap_id.bright_blue_italic()
@@ -1150,6 +2530,13 @@ fn attempt_transition_to_end(
                              accordant_syncer,
                              funding_info: Some(info),
                              auto_funded,
+                             funds_locked,
+                             local_trade_role,
+                             reconnect_attempts,
+                             txids,
+                             timeline,
+                             launched_at,
+                             nickname,
                          })))
                     })
                 } else {
@@ -1162,6 +2549,13 @@ fn attempt_transition_to_end(
                         accordant_syncer,
                         funding_info: Some(info),
                         auto_funded: false,
+                        funds_locked,
+                        local_trade_role,
+                        reconnect_attempts,
+                        txids,
+                        timeline,
+                        launched_at,
+                        nickname,
                     })))
                 }
             }
@@ -1169,6 +2563,12 @@ fn attempt_transition_to_end(
 
         (Request::FundingCompleted(blockchain), _) => {
             runtime.stats.incr_funded(&blockchain);
+            runtime.unconfirmed_locks.remove(&swap_id);
+            let funded = match blockchain {
+                Blockchain::Bitcoin => &mut timeline.funded_btc,
+                Blockchain::Monero => &mut timeline.funded_xmr,
+            };
+            funded.get_or_insert_with(|| unix_secs(SystemTime::now()));
             info!(
                 "{} | Your {} funding completed",
                 swap_id.bright_blue_italic(),
@@ -1183,25 +2583,60 @@ fn attempt_transition_to_end(
                 accordant_syncer,
                 funding_info: None,
                 auto_funded,
+                funds_locked: true,
+                local_trade_role,
+                reconnect_attempts,
+                txids,
+                timeline,
+                launched_at,
+                nickname,
             })))
         }
 
-        (Request::FundingCanceled(blockchain), _) => {
+        (Request::FundingReorged(blockchain), _) => {
+            runtime.stats.incr_funding_reorged(&blockchain);
+            warn!(
+                "{} | Your {} funding was reorged out of the chain, awaiting confirmations again",
+                swap_id.bright_blue_italic(),
+                blockchain.bright_green_bold()
+            );
+            Ok(Some(TradeStateMachine::SwapdRunning(SwapdRunning {
+                peerd,
+                public_offer,
+                swap_id,
+                connected: true,
+                arbitrating_syncer,
+                accordant_syncer,
+                funding_info: None,
+                auto_funded,
+                funds_locked: false,
+                local_trade_role,
+                reconnect_attempts,
+                txids,
+                timeline,
+                launched_at,
+                nickname,
+            })))
+        }
+
+        (Request::FundingCanceled(blockchain, reason), _) => {
             match blockchain {
                 Blockchain::Bitcoin => {
-                    runtime.stats.incr_funding_bitcoin_canceled();
+                    runtime.stats.incr_funding_bitcoin_canceled(reason);
                     info!(
-                        "{} | Your {} funding was canceled.",
+                        "{} | Your {} funding was canceled ({}).",
                         swap_id.bright_blue_italic(),
-                        blockchain.bright_green_bold()
+                        blockchain.bright_green_bold(),
+                        reason
                     );
                 }
                 Blockchain::Monero => {
-                    runtime.stats.incr_funding_monero_canceled();
+                    runtime.stats.incr_funding_monero_canceled(reason);
                     info!(
-                        "{} | Your {} funding was canceled.",
+                        "{} | Your {} funding was canceled ({}).",
                         swap_id.bright_blue_italic(),
-                        blockchain.bright_green_bold()
+                        blockchain.bright_green_bold(),
+                        reason
                     );
                 }
             };
@@ -1214,13 +2649,47 @@ fn attempt_transition_to_end(
                 accordant_syncer,
                 funding_info: None,
                 auto_funded,
+                funds_locked,
+                local_trade_role,
+                reconnect_attempts,
+                txids,
+                timeline,
+                launched_at,
+                nickname,
             })))
         }
 
         (Request::PeerdUnreachable(ServiceId::Peer(addr)), source)
             if ServiceId::Swap(swap_id) == source =>
         {
-            if runtime.registered_services.contains(&ServiceId::Peer(addr)) {
+            let reconnect_attempts = reconnect_attempts.saturating_add(1);
+            let max_reconnect_attempts = runtime.config.get_max_reconnect_attempts();
+            if reconnect_attempts > max_reconnect_attempts {
+                // only report the failure once, further reports are ignored until the
+                // counterparty reconnects by itself
+                if reconnect_attempts == max_reconnect_attempts + 1 {
+                    error!(
+                        "{} | Connection to the counterparty failed after {} reconnect attempts",
+                        swap_id.bright_blue_italic(),
+                        max_reconnect_attempts
+                    );
+                    let mut message = format!(
+                        "Connection to the counterparty failed after {} reconnect attempts",
+                        max_reconnect_attempts
+                    );
+                    if !funds_locked {
+                        message.push_str(&format!(
+                            ", no funds are locked yet, the swap can be aborted with `swap-cli abort-swap {}`",
+                            swap_id
+                        ));
+                    }
+                    runtime.push_progress(
+                        event.endpoints,
+                        ServiceId::Swap(swap_id),
+                        Request::Progress(Progress::Message(message)),
+                    );
+                }
+            } else if runtime.registered_services.contains(&ServiceId::Peer(addr)) {
                 warn!(
                     "Peerd {} was reported to be unreachable, attempting to
                     terminate to kick-off re-connect procedure, if we are
@@ -1238,19 +2707,38 @@ fn attempt_transition_to_end(
                 accordant_syncer,
                 funding_info,
                 auto_funded,
+                funds_locked,
+                local_trade_role,
+                reconnect_attempts,
+                txids,
+                timeline,
+                launched_at,
+                nickname,
             })))
         }
 
         (Request::SwapOutcome(outcome), source) if ServiceId::Swap(swap_id) == source => {
+            let counterparty = counterparty_node_id(local_trade_role, &public_offer, &peerd);
             event.send_ctl_service(
                 ServiceId::Database,
                 Request::SetOfferStatus(OfferStatusPair {
-                    offer: public_offer,
+                    offer: public_offer.clone(),
                     status: OfferStatus::Ended(outcome.clone()),
                 }),
             )?;
             runtime.clean_up_after_swap(&swap_id, event.endpoints)?;
             runtime.stats.incr_outcome(&outcome);
+            runtime.run_swap_outcome_hook(swap_id, &outcome, &public_offer);
+            runtime.notify_node_event(
+                event.endpoints,
+                NodeEvent::SwapEnded(swap_id, outcome.clone()),
+            );
+            timeline.ended = Some(unix_secs(SystemTime::now()));
+            timeline.outcome = Some(outcome.clone());
+            runtime.push_ended_swap_timeline(timeline);
+            if let Some(node_id) = counterparty {
+                runtime.record_peer_outcome(event.endpoints, node_id, &outcome)?;
+            }
             match outcome {
                 Outcome::Buy => {
                     debug!("Success on swap {}", swap_id);
@@ -1291,7 +2779,58 @@ fn attempt_transition_to_end(
                 accordant_syncer,
                 funding_info,
                 auto_funded,
+                funds_locked,
+                local_trade_role,
+                reconnect_attempts,
+                txids,
+                timeline,
+                launched_at,
+                nickname,
             })))
         }
     }
 }
+
+#[test]
+fn test_validate_timelocks() {
+    assert!(validate_timelocks(10, 30).is_ok());
+    assert!(validate_timelocks(MIN_TIMELOCK, MAX_TIMELOCK).is_ok());
+    // equal timelocks
+    assert!(validate_timelocks(10, 10).is_err());
+    // zero timelocks
+    assert!(validate_timelocks(0, 10).is_err());
+    assert!(validate_timelocks(10, 0).is_err());
+    // inverted ordering
+    assert!(validate_timelocks(30, 10).is_err());
+    // out of bounds
+    assert!(validate_timelocks(10, MAX_TIMELOCK + 1).is_err());
+}
+
+#[test]
+fn test_unsupported_fee_strategy() {
+    let fee_strategy = FeeStrategy::from_str(FARCASTER_DEFAULT_FEE_STRATEGY).unwrap();
+    assert!(unsupported_fee_strategy(&fee_strategy).is_none());
+    let fee_strategy = FeeStrategy::from_str("0 satoshi/vByte").unwrap();
+    assert!(unsupported_fee_strategy(&fee_strategy).is_some());
+}
+
+#[test]
+fn test_abort_body() {
+    let failure = Failure {
+        code: FailureCode::OfferExpiringSoon,
+        info: "the offer expires in 10s".to_string(),
+    };
+    let body = failure.to_abort_body();
+    assert_eq!(
+        Failure::from_abort_body(&body, FailureCode::CounterpartyAborted),
+        failure
+    );
+    // an abort of a node predating the encoding keeps its body as the reason
+    assert_eq!(
+        Failure::from_abort_body("no reason given", FailureCode::CounterpartyAborted),
+        Failure {
+            code: FailureCode::CounterpartyAborted,
+            info: "no reason given".to_string(),
+        }
+    );
+}
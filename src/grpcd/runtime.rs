@@ -1,9 +1,12 @@
+use crate::rpc::request::{NodeEvent, VersionInfo};
 use crate::service::Endpoints;
+use futures::Stream;
 use internet2::DuplexConnection;
 use internet2::Encrypt;
 use internet2::PlainTranscoder;
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::pin::Pin;
 use std::sync::Arc;
 use tokio::runtime::Builder;
 use tokio::sync::Mutex;
@@ -19,7 +22,7 @@ use microservices::ZMQ_CONTEXT;
 use std::sync::mpsc::{Receiver, Sender};
 
 use farcaster::farcaster_server::{Farcaster, FarcasterServer};
-use farcaster::{InfoRequest, InfoResponse};
+use farcaster::{InfoRequest, InfoResponse, NodeEventResponse, SubscribeNodeEventsRequest};
 use tonic::{transport::Server, Request as GrpcRequest, Response as GrpcResponse, Status};
 
 pub mod farcaster {
@@ -63,6 +66,7 @@ pub fn run(config: ServiceConfig, grpc_port: u64) -> Result<(), Error> {
 pub struct FarcasterService {
     tokio_tx_request: tokio::sync::mpsc::Sender<(u64, Request)>,
     pending_requests: Arc<Mutex<HashMap<u64, tokio::sync::oneshot::Sender<Request>>>>,
+    event_subscriptions: Arc<Mutex<HashMap<u64, tokio::sync::mpsc::Sender<Request>>>>,
     id_counter: Arc<Mutex<IdCounter>>,
 }
 
@@ -88,6 +92,8 @@ impl Farcaster for FarcasterService {
         drop(pending_requests);
         match oneshot_rx.await {
             Ok(Request::NodeInfo(info)) => {
+                // grpcd is built along farcasterd, so it reports the version of the node
+                let version = VersionInfo::current();
                 let reply = farcaster::InfoResponse {
                     id: request.into_inner().id,
                     listens: info
@@ -104,6 +110,14 @@ impl Farcaster for FarcasterService {
                         .iter()
                         .map(|offer| format!("{}", offer))
                         .collect(),
+                    node_id: info
+                        .node_id
+                        .map(|node_id| format!("{}", node_id))
+                        .unwrap_or_default(),
+                    alias: info.alias.unwrap_or_default(),
+                    version: version.version,
+                    git_commit: version.git_commit.unwrap_or_default(),
+                    farcaster_core_version: version.farcaster_core_version,
                 };
                 Ok(GrpcResponse::new(reply))
             }
@@ -111,6 +125,92 @@ impl Farcaster for FarcasterService {
             _ => Err(Status::invalid_argument("received invalid response")),
         }
     }
+
+    type SubscribeNodeEventsStream =
+        Pin<Box<dyn Stream<Item = Result<NodeEventResponse, Status>> + Send>>;
+
+    async fn subscribe_node_events(
+        &self,
+        request: GrpcRequest<SubscribeNodeEventsRequest>,
+    ) -> Result<GrpcResponse<Self::SubscribeNodeEventsStream>, Status> {
+        println!("Got a request: {:?}", request);
+        let request_id = request.into_inner().id;
+
+        let mut id_counter = self.id_counter.lock().await;
+        let id = id_counter.increment();
+        drop(id_counter);
+
+        // the subscription is registered before subscribing, so no event is missed
+        let (events_tx, events_rx) = tokio::sync::mpsc::channel::<Request>(1000);
+        let mut event_subscriptions = self.event_subscriptions.lock().await;
+        event_subscriptions.insert(id, events_tx);
+        drop(event_subscriptions);
+
+        if let Err(error) = self
+            .tokio_tx_request
+            .send((id, Request::SubscribeNodeEvents))
+            .await
+        {
+            self.event_subscriptions.lock().await.remove(&id);
+            return Err(Status::internal(format!("{}", error)));
+        }
+
+        let stream = futures::stream::unfold(events_rx, move |mut events_rx| async move {
+            loop {
+                match events_rx.recv().await? {
+                    Request::NodeEvent(event) => {
+                        return Some((Ok(node_event_response(request_id, event)), events_rx));
+                    }
+                    // the subscription was refused, end the stream after reporting it
+                    Request::Failure(failure) => {
+                        events_rx.close();
+                        return Some((Err(Status::internal(failure.info)), events_rx));
+                    }
+                    request => {
+                        debug!("ignoring {} on the node event stream", request);
+                    }
+                }
+            }
+        });
+        Ok(GrpcResponse::new(Box::pin(stream)))
+    }
+}
+
+fn node_event_response(id: u32, event: NodeEvent) -> NodeEventResponse {
+    let mut response = NodeEventResponse {
+        id,
+        ..Default::default()
+    };
+    match event {
+        NodeEvent::OfferMade(offer) => {
+            response.event = "offer_made".to_string();
+            response.offer_id = offer.id().to_string();
+            response.offer = offer.to_string();
+        }
+        NodeEvent::OfferRevoked(offer) => {
+            response.event = "offer_revoked".to_string();
+            response.offer_id = offer.id().to_string();
+            response.offer = offer.to_string();
+        }
+        NodeEvent::SwapStarted(swap_id) => {
+            response.event = "swap_started".to_string();
+            response.swap_id = swap_id.to_string();
+        }
+        NodeEvent::SwapEnded(swap_id, outcome) => {
+            response.event = "swap_ended".to_string();
+            response.swap_id = swap_id.to_string();
+            response.outcome = outcome.to_string();
+        }
+        NodeEvent::PeerConnected(peer) => {
+            response.event = "peer_connected".to_string();
+            response.peer = peer.to_string();
+        }
+        NodeEvent::PeerDisconnected(peer) => {
+            response.event = "peer_disconnected".to_string();
+            response.peer = peer.to_string();
+        }
+    }
+    response
 }
 
 pub struct GrpcServer {
@@ -145,17 +245,40 @@ fn request_loop(
 fn response_loop(
     mpsc_rx_response: Receiver<(u64, Request)>,
     pending_requests_lock: Arc<Mutex<HashMap<u64, tokio::sync::oneshot::Sender<Request>>>>,
+    event_subscriptions_lock: Arc<Mutex<HashMap<u64, tokio::sync::mpsc::Sender<Request>>>>,
+    tokio_tx_request: tokio::sync::mpsc::Sender<(u64, Request)>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn(async move {
         loop {
             let response = mpsc_rx_response.try_recv();
             if let Ok((id, request)) = response {
                 let mut pending_requests = pending_requests_lock.lock().await;
+                let mut event_subscriptions = event_subscriptions_lock.lock().await;
                 if pending_requests.contains_key(&id) {
                     let sender = pending_requests.remove(&id).unwrap();
                     sender
                         .send(request)
                         .expect("unable to send response from grpc response loop to its handler");
+                } else if let Some(events_tx) = event_subscriptions.get(&id) {
+                    match events_tx.try_send(request) {
+                        Ok(()) => {}
+                        Err(tokio::sync::mpsc::error::TrySendError::Full(request)) => {
+                            warn!(
+                                "grpc client {} lags behind, dropping node event {}",
+                                id, request
+                            );
+                        }
+                        // the grpc client is gone, stop forwarding it the node events
+                        Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) => {
+                            event_subscriptions.remove(&id);
+                            if let Err(error) = tokio_tx_request
+                                .send((id, Request::UnsubscribeNodeEvents))
+                                .await
+                            {
+                                error!("could not unsubscribe grpc client {}: {}", id, error);
+                            }
+                        }
+                    }
                 } else {
                     error!("id {} not found in pending grpc requests", id);
                 }
@@ -198,13 +321,22 @@ impl GrpcServer {
                 let pending_requests: Arc<
                     Mutex<HashMap<u64, tokio::sync::oneshot::Sender<Request>>>,
                 > = Arc::new(Mutex::new(map![]));
+                let event_subscriptions: Arc<
+                    Mutex<HashMap<u64, tokio::sync::mpsc::Sender<Request>>>,
+                > = Arc::new(Mutex::new(map![]));
                 let request_handle = request_loop(tokio_rx_request, tx_request);
-                let response_handle = response_loop(rx_response, Arc::clone(&pending_requests));
+                let response_handle = response_loop(
+                    rx_response,
+                    Arc::clone(&pending_requests),
+                    Arc::clone(&event_subscriptions),
+                    tokio_tx_request.clone(),
+                );
 
                 let service = FarcasterService {
                     id_counter: Arc::new(Mutex::new(IdCounter(0))),
                     tokio_tx_request,
                     pending_requests,
+                    event_subscriptions,
                 };
 
                 let server_handle = server_loop(service, addr);
@@ -284,6 +416,10 @@ impl Runtime {
             Request::Hello => {
                 debug!("Received Hello from {}", source);
             }
+            // not a response, the grpc client already got its reply when making or taking the offer
+            Request::SwapStarted(swap_started) => {
+                debug!("Received swap started notification {}", swap_started);
+            }
             _ => {
                 if let ServiceId::GrpcdClient(id) = source {
                     self.tx_response
@@ -97,6 +97,21 @@ pub struct Opts {
         default_value = FARCASTER_CTL_SOCKET_NAME
     )]
     pub ctl_socket: ServiceAddr,
+
+    /// High-water mark of the outgoing queues of the ZMQ bus sockets
+    ///
+    /// Maximum number of messages queued towards a connected service, ZMQ
+    /// drops the further messages routed to it once reached. Zero means no
+    /// limit. Defaults to the configured value, or the ZMQ default of 1000.
+    #[clap(long, global = true, env = "FARCASTER_ZMQ_SEND_HWM")]
+    pub zmq_send_hwm: Option<i32>,
+
+    /// High-water mark of the incoming queues of the ZMQ bus sockets
+    ///
+    /// Maximum number of messages queued from a connected service. Zero means
+    /// no limit. Defaults to the configured value, or the ZMQ default of 1000.
+    #[clap(long, global = true, env = "FARCASTER_ZMQ_RECEIVE_HWM")]
+    pub zmq_receive_hwm: Option<i32>,
 }
 
 /// Token used in services
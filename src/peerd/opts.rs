@@ -67,6 +67,14 @@ pub struct Opts {
     #[clap(short, long, default_value = "9735")]
     pub port: u16,
 
+    /// Announce the peer protocol version to the remote peer
+    ///
+    /// Nodes predating the version exchange drop the connection on the
+    /// announcement, farcasterd sets it when the exchange is enabled in its
+    /// configuration.
+    #[clap(long)]
+    pub announce_protocol_version: bool,
+
     /// Node key configuration
     #[clap(flatten)]
     pub peer_key_opts: PeerKeyOpts,
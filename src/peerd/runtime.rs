@@ -34,7 +34,7 @@ use microservices::peer::{self, PeerConnection, PeerSender, SendMessage};
 use microservices::ZMQ_CONTEXT;
 
 use crate::rpc::{
-    request::{Msg, PeerInfo},
+    request::{self, Msg, PeerInfo},
     Request, ServiceBus,
 };
 use crate::{CtlServer, Endpoints, Error, LogStyle, Service, ServiceConfig, ServiceId};
@@ -48,6 +48,7 @@ pub fn run(
     local_node: LocalNode,
     // TODO: make this an enum instead with a descriptive distinction of listening and connecting to a listener
     forked_from_listener: bool,
+    announce_protocol_version: bool,
 ) -> Result<(), Error> {
     debug!("Splitting connection into receiver and sender parts");
     let (mut peer_receiver, mut peer_sender) = connection.split();
@@ -89,6 +90,13 @@ pub fn run(
         })
     };
 
+    // announce our protocol version, the remote peer announces its own in return
+    if announce_protocol_version {
+        peer_sender
+            .send_message(Msg::Version(request::PROTOCOL_VERSION))
+            .expect("failed to send protocol version to the remote peer");
+    }
+
     debug!("Opening bridge between runtime and peer receiver threads");
     let rx = ZMQ_CONTEXT.socket(zmq::PULL)?;
     rx.bind("inproc://bridge")?;
@@ -146,6 +154,8 @@ pub fn run(
         messages_sent: 0,
         messages_received: 0,
         awaited_pong: None,
+        protocol_version: None,
+        announce_protocol_version,
         thread_flag_tx,
     };
     let mut service = Service::service(config, runtime)?;
@@ -282,6 +292,8 @@ pub struct Runtime {
     messages_sent: usize,
     messages_received: usize,
     awaited_pong: Option<u16>,
+    protocol_version: Option<u16>,
+    announce_protocol_version: bool,
 
     thread_flag_tx: std::sync::mpsc::Sender<()>,
 }
@@ -413,6 +425,7 @@ impl Runtime {
                     messages_received: self.messages_received,
                     forked_from_listener: self.forked_from_listener,
                     awaits_pong: self.awaited_pong.is_some(),
+                    protocol_version: self.protocol_version,
                 };
                 self.send_ctl(endpoints, source, Request::PeerInfo(info))?;
             }
@@ -452,6 +465,10 @@ impl Runtime {
         // send the local id to the maker(listener) again
         self.peer_sender
             .send_message(Msg::Identity(self.local_node.node_id()))?;
+        if self.announce_protocol_version {
+            self.peer_sender
+                .send_message(Msg::Version(request::PROTOCOL_VERSION))?;
+        }
 
         let identity = self.identity.clone();
         let dying_thread_flag_tx = self.thread_flag_tx.clone();
@@ -501,6 +518,29 @@ impl Runtime {
                 self.awaited_pong = None;
             }
 
+            Request::Protocol(Msg::Version(remote_version)) => {
+                let negotiated = request::negotiate_protocol_version(*remote_version);
+                if request::is_compatible_protocol_version(negotiated) {
+                    info!(
+                        "Negotiated protocol version {} with the remote peer",
+                        negotiated.bright_white_bold()
+                    );
+                } else {
+                    warn!(
+                        "The remote peer speaks protocol version {}, swaps require at least version {}",
+                        remote_version,
+                        request::MIN_PROTOCOL_VERSION
+                    );
+                }
+                self.protocol_version = Some(negotiated);
+                endpoints.send_to(
+                    ServiceBus::Ctl,
+                    self.identity(),
+                    ServiceId::Farcasterd,
+                    Request::PeerdProtocolVersion(negotiated),
+                )?;
+            }
+
             Request::Protocol(Msg::PeerReceiverRuntimeShutdown) => {
                 warn!("Exiting peerd receiver runtime");
                 // If this is the listener-forked peerd, i.e. the maker's peerd, terminate it.
@@ -19,9 +19,11 @@ use std::time::Duration;
 use internet2::ZmqSocketType;
 use microservices::esb;
 
+use crate::rpc::request::{NodeEvent, Outcome, ProgressEvent, SwapTimeline};
 use crate::rpc::{Request, ServiceBus};
 use crate::service::ServiceConfig;
 use crate::{Error, LogStyle, ServiceId};
+use farcaster_core::swap::SwapId;
 
 #[repr(C)]
 pub struct Client {
@@ -111,10 +113,46 @@ impl Client {
                     // terminate on success
                     break Ok(());
                 }
+                // a batched subscription receives the events coalesced, in order
+                Ok(Request::ProgressBatch(batch)) => {
+                    for event in batch.progress {
+                        match event {
+                            ProgressEvent::Failure(fail) => {
+                                return Err(Error::Farcaster(fail.info))
+                            }
+                            ProgressEvent::Success(s) => {
+                                println!("{}", s.bright_green_bold());
+                                return Ok(());
+                            }
+                            ProgressEvent::Message(msg) | ProgressEvent::StateTransition(msg) => {
+                                println!("{}", msg)
+                            }
+                        }
+                    }
+                }
                 Ok(req) => println!("{}", req),
             }
         }
     }
+
+    /// Print the stream of received progress until the swap reaches its outcome, reported by
+    /// the swap ended node event, or by the timeline of a swap that already ended, or until its
+    /// progress fails
+    pub fn wait_outcome(&mut self, swap_id: SwapId) -> Result<Outcome, Error> {
+        loop {
+            match self.report_failure()? {
+                Request::NodeEvent(NodeEvent::SwapEnded(id, outcome)) if id == swap_id => {
+                    break Ok(outcome);
+                }
+                Request::SwapTimelineReport(SwapTimeline {
+                    outcome: Some(outcome),
+                    ..
+                }) => break Ok(outcome),
+                Request::NodeEvent(_) | Request::SwapTimelineReport(_) => {}
+                req => println!("{}", req),
+            }
+        }
+    }
 }
 
 pub struct Handler {
@@ -16,7 +16,7 @@
 
 use crate::cli::OfferSelector;
 use crate::swapd::CheckpointSwapd;
-use crate::syncerd::{Event, SweepAddressAddendum, Task};
+use crate::syncerd::{Event, SweepAddressAddendum, Task, TaskId};
 use crate::walletd::runtime::CheckpointWallet;
 use amplify::{ToYamlString, Wrapper};
 use internet2::{CreateUnmarshaller, Unmarshaller};
@@ -34,24 +34,46 @@ use bitcoin::{
         rand::{thread_rng, RngCore},
         SecretKey,
     },
-    OutPoint, Transaction,
+    OutPoint, Transaction, Txid,
 };
 use farcaster_core::{
     blockchain::Blockchain,
     protocol::message::Abort,
-    role::TradeRole,
+    role::{SwapRole, TradeRole},
     swap::btcxmr::message::{
         BuyProcedureSignature, CommitAliceParameters, CommitBobParameters, CoreArbitratingSetup,
         RefundProcedureSignatures, RevealAliceParameters, RevealBobParameters, RevealProof,
     },
     swap::btcxmr::{Offer, Parameters, PublicOffer},
     swap::SwapId,
+    transaction::TxLabel,
 };
 use internet2::addr::{InetSocketAddr, NodeAddr};
 use internet2::Api;
 use microservices::rpc;
 use strict_encoding::{StrictDecode, StrictEncode};
 
+/// Version of the peer protocol spoken by this node, announced to the remote peer on connection
+pub const PROTOCOL_VERSION: u16 = 1;
+
+/// Lowest negotiated peer protocol version swaps can be run with when the version exchange is
+/// enabled, peers predating the exchange are below it
+pub const MIN_PROTOCOL_VERSION: u16 = 1;
+
+/// Protocol version assumed for a remote peer that did not announce one, i.e. a node predating
+/// the version exchange
+pub const LEGACY_PROTOCOL_VERSION: u16 = 0;
+
+/// Returns the protocol version negotiated with a remote peer announcing the given version
+pub fn negotiate_protocol_version(remote_version: u16) -> u16 {
+    remote_version.min(PROTOCOL_VERSION)
+}
+
+/// Returns true if swaps can be run over a connection with the negotiated protocol version
+pub fn is_compatible_protocol_version(negotiated_version: u16) -> bool {
+    negotiated_version >= MIN_PROTOCOL_VERSION
+}
+
 #[derive(Clone, Debug, Display, From, StrictDecode, StrictEncode, Api)]
 #[api(encoding = "strict")]
 #[display(inner)]
@@ -103,6 +125,10 @@ pub enum Msg {
     #[api(type = 35)]
     #[display("identity(..)")]
     Identity(internet2::addr::NodeId),
+
+    #[api(type = 37)]
+    #[display("version({0})")]
+    Version(u16),
 }
 
 impl Msg {
@@ -126,9 +152,10 @@ impl Msg {
             | Msg::Pong(_)
             | Msg::PingPeer
             | Msg::PeerReceiverRuntimeShutdown
-            | Msg::Identity(_) => {
+            | Msg::Identity(_)
+            | Msg::Version(_) => {
                 unreachable!(
-                    "Ping, Pong, PingPeer, PeerdShutdown and Identity do not contain swapid"
+                    "Ping, Pong, PingPeer, PeerdShutdown, Identity and Version do not contain swapid"
                 )
             }
         }
@@ -145,6 +172,7 @@ impl Msg {
                 | Msg::BuyProcedureSignature(_)
                 | Msg::Ping(_)
                 | Msg::Pong(_)
+                | Msg::Version(_)
         )
     }
 }
@@ -183,6 +211,7 @@ pub struct PubOffer {
     pub public_offer: PublicOffer,
     pub external_address: bitcoin::Address,
     pub internal_address: monero::Address,
+    pub syncer_servers: SyncerServersOverride,
 }
 
 impl From<(PublicOffer, bitcoin::Address, monero::Address)> for PubOffer {
@@ -192,11 +221,24 @@ impl From<(PublicOffer, bitcoin::Address, monero::Address)> for PubOffer {
             public_offer,
             external_address,
             internal_address,
+            syncer_servers: none!(),
         }
     }
 }
 
-#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+/// Syncer servers used by a single swap instead of the ones configured for its network, unset
+/// servers fall back to the configured ones
+#[derive(Clone, PartialEq, Eq, Debug, Default, Display, StrictEncode, StrictDecode)]
+#[display(Debug)]
+pub struct SyncerServersOverride {
+    pub electrum_server: Option<String>,
+    pub monero_daemon: Option<String>,
+    pub monero_rpc_wallet: Option<String>,
+    /// Named syncer instance of the configuration serving the swap, exclusive with the servers
+    pub syncer_instance: Option<String>,
+}
+
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq, Hash)]
 #[display("{0}")]
 pub struct Token(pub String);
 
@@ -219,6 +261,33 @@ pub struct LaunchSwap {
     pub funding_address: Option<bitcoin::Address>,
 }
 
+/// Number of confirmations a running swap requires before considering its transactions on the
+/// given blockchain final
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode)]
+#[display("{swap_id}, {blockchain}, {confs}")]
+pub struct ConfirmationTarget {
+    pub swap_id: SwapId,
+    pub blockchain: Blockchain,
+    pub confs: u32,
+}
+
+/// Bind a running swap to another connection with its counterparty, e.g. after a reconnection
+/// the swap did not pick up
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode)]
+#[display("{swap_id}, {new_peer}")]
+pub struct RebindSwapConnection {
+    pub swap_id: SwapId,
+    pub new_peer: NodeAddr,
+}
+
+/// Revoke an open offer, or with `dry_run` only report what revoking it would affect
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode)]
+#[display("{public_offer}, dry_run: {dry_run}")]
+pub struct RevokeOffer {
+    pub public_offer: PublicOffer,
+    pub dry_run: bool,
+}
+
 #[derive(Clone, Debug, Display, From, StrictDecode, StrictEncode)]
 #[display("{swap_id}, ..")]
 pub struct TakeCommit {
@@ -358,6 +427,18 @@ pub enum Request {
     #[display("swap_outcome({0})")]
     SwapOutcome(Outcome),
 
+    #[api(type = 47)]
+    #[display("swap_message_stats({0})")]
+    SwapMessageStats(MessageStats),
+
+    #[api(type = 48)]
+    #[display("swap_txid({0})")]
+    SwapTxid(SwapTxid),
+
+    #[api(type = 49)]
+    #[display("peerd_protocol_version({0})")]
+    PeerdProtocolVersion(u16),
+
     #[api(type = 100)]
     #[display("get_info()")]
     GetInfo,
@@ -382,6 +463,190 @@ pub enum Request {
     #[display("list_listens()")]
     ListListens,
 
+    #[api(type = 106)]
+    #[display("listen_offers({0})")]
+    ListenOffers(InetSocketAddr),
+
+    #[api(type = 107)]
+    #[display("swap_state_dump({0})")]
+    SwapStateDump(SwapId),
+
+    #[api(type = 108)]
+    #[display("get_syncer_servers({0})")]
+    GetSyncerServers(Network),
+
+    #[api(type = 109)]
+    #[display("list_restorable()")]
+    ListRestorable,
+
+    #[api(type = 110)]
+    #[display("test_listen_reachability()")]
+    TestListenReachability,
+
+    #[api(type = 111)]
+    #[display("swap_transactions({0})")]
+    SwapTransactions(SwapId),
+
+    #[api(type = 112)]
+    #[display("swap_info_batch(..)")]
+    SwapInfoBatch(Vec<SwapId>),
+
+    #[api(type = 113)]
+    #[display("syncer_clients()")]
+    SyncerClients,
+
+    #[api(type = 114)]
+    #[display("set_auto_funding({0})")]
+    SetAutoFunding(bool),
+
+    #[api(type = 115)]
+    #[display("list_state_machines()")]
+    ListStateMachines,
+
+    #[api(type = 116)]
+    #[display("set_swap_nickname({0}, {1})")]
+    SetSwapNickname(SwapId, String),
+
+    #[api(type = 117)]
+    #[display("service_inventory()")]
+    ServiceInventory,
+
+    #[api(type = 118)]
+    #[display("total_value_locked()")]
+    TotalValueLocked,
+
+    #[api(type = 119)]
+    #[display("get_peer_reputation({0})")]
+    GetPeerReputation(bitcoin::secp256k1::PublicKey),
+
+    #[api(type = 120)]
+    #[display("resync_swap({0})")]
+    ResyncSwap(SwapId),
+
+    #[api(type = 121)]
+    #[display("swap_timeline({0})")]
+    SwapTimeline(SwapId),
+
+    #[api(type = 122)]
+    #[display("pause_syncer({0}, {1})")]
+    PauseSyncer(Blockchain, Network),
+
+    #[api(type = 123)]
+    #[display("resume_syncer({0}, {1})")]
+    ResumeSyncer(Blockchain, Network),
+
+    #[api(type = 124)]
+    #[display("offer_book()")]
+    OfferBook,
+
+    #[api(type = 125)]
+    #[display("rebind_swap_connection({0})")]
+    RebindSwapConnection(RebindSwapConnection),
+
+    #[api(type = 126)]
+    #[display("funding_cancellation_breakdown()")]
+    FundingCancellationBreakdown,
+
+    #[api(type = 127)]
+    #[display("validate_config()")]
+    ValidateConfig,
+
+    #[api(type = 128)]
+    #[display("active_networks()")]
+    ActiveNetworks,
+
+    #[api(type = 129)]
+    #[display("swap_counterparty({0})")]
+    SwapCounterparty(SwapId),
+
+    #[api(type = 130)]
+    #[display("query_sweep_status({0})")]
+    QuerySweepStatus(TaskId),
+
+    #[api(type = 131)]
+    #[display("swap_state_histogram()")]
+    SwapStateHistogram,
+
+    #[api(type = 132)]
+    #[display("get_paths()")]
+    GetPaths,
+
+    #[api(type = 133)]
+    #[display("has_seen_offer({0})")]
+    HasSeenOffer(HasSeenOffer),
+
+    #[api(type = 134)]
+    #[display("throughput_stats()")]
+    ThroughputStats,
+
+    #[api(type = 135)]
+    #[display("get_unprocessed_requests({0})")]
+    GetUnprocessedRequests(GetUnprocessedRequests),
+
+    #[api(type = 136)]
+    #[display("verify_node_keys()")]
+    VerifyNodeKeys,
+
+    #[api(type = 137)]
+    #[display("offers_by_maker({0})")]
+    OffersByMaker(bitcoin::secp256k1::PublicKey),
+
+    #[api(type = 138)]
+    #[display("teardown_preview({0})")]
+    TeardownPreview(ServiceId),
+
+    #[api(type = 139)]
+    #[display("swap_state_dwell({0})")]
+    SwapStateDwell(SwapId),
+
+    #[api(type = 140)]
+    #[display("reissue_swap_tasks({0})")]
+    ReissueSwapTasks(SwapId),
+
+    #[api(type = 141)]
+    #[display("startup_info")]
+    StartupInfo,
+
+    #[api(type = 142)]
+    #[display("test_launch({0})")]
+    TestLaunch(String),
+
+    #[api(type = 143)]
+    #[display("version")]
+    Version,
+
+    #[api(type = 144)]
+    #[display("cancel_pending_restores")]
+    CancelPendingRestores,
+
+    #[api(type = 145)]
+    #[display("subscribe_progress_batched({0})")]
+    SubscribeProgressBatched(SwapId),
+
+    #[api(type = 146)]
+    #[display("swap_timelocks({0})")]
+    SwapTimelocks(SwapId),
+
+    #[api(type = 147)]
+    #[display("refresh_connections")]
+    RefreshConnections,
+
+    #[api(type = 148)]
+    #[display("reload_syncer_servers({0})")]
+    ReloadSyncerServers(Network),
+
+    #[api(type = 149)]
+    #[display("subscription_topology()")]
+    SubscriptionTopology,
+
+    #[api(type = 150)]
+    #[display("list_peer_protocol_versions()")]
+    ListPeerProtocolVersions,
+
+    #[api(type = 151)]
+    #[display("list_offers_by_direction({0})")]
+    ListOffersByDirection(ListOffers),
+
     #[api(type = 200)]
     #[display("listen({0})")]
     Listen(InetSocketAddr),
@@ -428,7 +693,7 @@ pub enum Request {
 
     #[api(type = 193)]
     #[display("revoke_offer({0})")]
-    RevokeOffer(PublicOffer),
+    RevokeOffer(RevokeOffer),
 
     #[api(type = 192)]
     #[display("abort_swap()")]
@@ -438,6 +703,26 @@ pub enum Request {
     #[display("fund_swap({0})")]
     FundSwap(OutPoint),
 
+    #[api(type = 208)]
+    #[display("kill_swap({0})")]
+    KillSwap(SwapId),
+
+    #[api(type = 209)]
+    #[display("authenticate(..)")]
+    Authenticate(Token),
+
+    #[api(type = 210)]
+    #[display("drain({0})")]
+    Drain(bool),
+
+    #[api(type = 211)]
+    #[display("set_confirmation_target({0})")]
+    SetConfirmationTarget(ConfirmationTarget),
+
+    #[api(type = 213)]
+    #[display("swap_clients(..)")]
+    SwapClients(Vec<ServiceId>),
+
     // Progress functionalities
     // ----------------
     #[api(type = 1003)]
@@ -452,6 +737,18 @@ pub enum Request {
     #[display("unsubscribe_progress({0})")]
     UnsubscribeProgress(SwapId),
 
+    #[api(type = 1008)]
+    #[display("resend_progress({0})")]
+    ResendProgress(SwapId),
+
+    #[api(type = 1009)]
+    #[display("subscribe_node_events()")]
+    SubscribeNodeEvents,
+
+    #[api(type = 1010)]
+    #[display("unsubscribe_node_events()")]
+    UnsubscribeNodeEvents,
+
     // Responses to CLI
     // ----------------
     #[api(type = 1004)]
@@ -466,6 +763,10 @@ pub enum Request {
     #[display(inner)]
     TookOffer(TookOffer),
 
+    #[api(type = 212)]
+    #[display(inner)]
+    SwapStarted(SwapStarted),
+
     #[api(type = 1002)]
     #[display(inner)]
     Progress(Progress),
@@ -474,6 +775,11 @@ pub enum Request {
     #[display(inner)]
     SwapProgress(SwapProgress),
 
+    #[api(type = 1011)]
+    #[display(inner)]
+    #[from]
+    NodeEvent(NodeEvent),
+
     #[api(type = 1001)]
     #[display(inner)]
     Success(OptionDetails),
@@ -546,6 +852,172 @@ pub enum Request {
     #[display("needs_funding({0})")]
     NeedsFunding(Blockchain),
 
+    #[api(type = 1116)]
+    #[display("all_funding_needs()")]
+    AllFundingNeeds,
+
+    #[api(type = 1117)]
+    #[display(inner)]
+    #[from]
+    FundingNeeds(FundingNeeds),
+
+    #[api(type = 1118)]
+    #[display(inner)]
+    ListenReachabilityList(List<ListenReachability>),
+
+    #[api(type = 1119)]
+    #[display(inner)]
+    RevokeImpact(RevokeImpact),
+
+    #[api(type = 1120)]
+    #[display(inner)]
+    SwapTxidList(List<SwapTxid>),
+
+    #[api(type = 1121)]
+    #[display(inner)]
+    SwapInfoList(List<SwapInfo>),
+
+    #[api(type = 1122)]
+    #[display(inner)]
+    SyncerClientList(List<SyncerClients>),
+
+    #[api(type = 1123)]
+    #[display(inner)]
+    #[from]
+    StateMachines(StateMachines),
+
+    #[api(type = 1124)]
+    #[display(inner)]
+    #[from]
+    ServiceInventoryReport(ServiceInventoryReport),
+
+    #[api(type = 1125)]
+    #[display(inner)]
+    #[from]
+    ValueLocked(ValueLocked),
+
+    #[api(type = 1126)]
+    #[display(inner)]
+    #[from]
+    PeerReputation(PeerReputation),
+
+    #[api(type = 1127)]
+    #[display(inner)]
+    #[from]
+    SwapTimelineReport(SwapTimeline),
+
+    #[api(type = 1128)]
+    #[display(inner)]
+    #[from]
+    OfferBookReport(OfferBook),
+
+    #[api(type = 1129)]
+    #[display(inner)]
+    #[from]
+    FundingCancellationBreakdownReport(FundingCancellationBreakdown),
+
+    #[api(type = 1130)]
+    #[display(inner)]
+    #[from]
+    ConfigValidation(ConfigValidation),
+
+    #[api(type = 1131)]
+    #[display(inner)]
+    #[from]
+    ActiveNetworksReport(ActiveNetworks),
+
+    #[api(type = 1132)]
+    #[display(inner)]
+    #[from]
+    SwapCounterpartyReport(SwapCounterparty),
+
+    #[api(type = 1133)]
+    #[display(inner)]
+    #[from]
+    SwapStateHistogramReport(SwapStateHistogram),
+
+    #[api(type = 1134)]
+    #[display(inner)]
+    #[from]
+    NodePaths(NodePaths),
+
+    #[api(type = 1135)]
+    #[display(inner)]
+    #[from]
+    OfferSeen(OfferSeen),
+
+    #[api(type = 1136)]
+    #[display(inner)]
+    #[from]
+    ThroughputStatsReport(ThroughputStats),
+
+    #[api(type = 1137)]
+    #[display(inner)]
+    #[from]
+    UnprocessedRequests(UnprocessedRequests),
+
+    #[api(type = 1138)]
+    #[display(inner)]
+    #[from]
+    MakerOfferList(List<MakerOffer>),
+
+    #[api(type = 1139)]
+    #[display(inner)]
+    #[from]
+    TeardownPreviewReport(TeardownPreview),
+
+    #[api(type = 1140)]
+    #[display(inner)]
+    #[from]
+    SwapStateDwellReport(SwapStateDwell),
+
+    #[api(type = 1141)]
+    #[display(inner)]
+    #[from]
+    StartupInfoReport(StartupInfo),
+
+    #[api(type = 1142)]
+    #[display(inner)]
+    #[from]
+    TestLaunchReport(TestLaunch),
+
+    #[api(type = 1143)]
+    #[display(inner)]
+    #[from]
+    VersionInfo(VersionInfo),
+
+    #[api(type = 1144)]
+    #[display(inner)]
+    #[from]
+    CancelledRestores(CancelledRestores),
+
+    #[api(type = 1145)]
+    #[display("progress_batch({0})")]
+    ProgressBatch(SwapProgress),
+
+    #[api(type = 1146)]
+    #[display(inner)]
+    #[from]
+    SwapTimelocksReport(SwapTimelocks),
+
+    #[api(type = 1147)]
+    #[display(inner)]
+    #[from]
+    ConnectionsRefreshed(ConnectionsRefreshed),
+
+    #[api(type = 1148)]
+    #[display(inner)]
+    #[from]
+    SyncerServersReloaded(SyncerServersReload),
+
+    #[api(type = 1149)]
+    #[display(inner)]
+    ProgressSubscriptionList(List<ProgressSubscriptions>),
+
+    #[api(type = 1150)]
+    #[display(inner)]
+    PeerProtocolVersionList(List<ConnectedPeer>),
+
     #[api(type = 1110)]
     #[display("write_text")]
     WriteText(List<String>),
@@ -555,8 +1027,22 @@ pub enum Request {
     FundingCompleted(Blockchain),
 
     #[api(type = 1112)]
-    #[display("funding_canceled({0})")]
-    FundingCanceled(Blockchain),
+    #[display("funding_canceled({0}, {1})")]
+    FundingCanceled(Blockchain, FundingCancelReason),
+
+    #[api(type = 1113)]
+    #[display("funding_reorged({0})")]
+    FundingReorged(Blockchain),
+
+    #[api(type = 1114)]
+    #[display(inner)]
+    #[from]
+    TradeStateDump(TradeStateDump),
+
+    #[api(type = 1115)]
+    #[display(inner)]
+    #[from]
+    SyncerServersInfo(SyncerServersInfo),
 
     // #[api(type = 1203)]
     // #[display("channel_funding({0})", alt = "{0:#}")]
@@ -642,6 +1128,58 @@ pub enum Request {
     #[api(type = 1319)]
     #[display("address_secret_key")]
     AddressSecretKey(AddressSecretKey),
+
+    #[api(type = 1320)]
+    #[display("validate_checkpoint({0})")]
+    ValidateCheckpoint(SwapId),
+
+    #[api(type = 1321)]
+    #[display("checkpoint_validation({0})")]
+    CheckpointValidation(CheckpointValidation),
+
+    #[api(type = 1322)]
+    #[display("log_stats()")]
+    LogStats,
+
+    #[api(type = 1323)]
+    #[display("reap_idle_services()")]
+    ReapIdleServices,
+
+    #[api(type = 1324)]
+    #[display("check_startup()")]
+    CheckStartup,
+
+    #[api(type = 1325)]
+    #[display("set_peer_reputation({0})")]
+    SetPeerReputation(PeerReputation),
+
+    #[api(type = 1326)]
+    #[display("retrieve_peer_reputations()")]
+    RetrievePeerReputations,
+
+    #[api(type = 1327)]
+    #[display("peer_reputation_list({0})")]
+    PeerReputationList(List<PeerReputation>),
+
+    #[api(type = 1328)]
+    #[display("check_swapds()")]
+    CheckSwapds,
+
+    #[api(type = 1329)]
+    #[display("check_listeners()")]
+    CheckListeners,
+
+    #[api(type = 1330)]
+    #[display("flush_progress_batches()")]
+    FlushProgressBatches,
+
+    #[api(type = 1331)]
+    #[display("check_connection_refresh()")]
+    CheckConnectionRefresh,
+
+    #[api(type = 1332)]
+    #[display("deliver_background_replies()")]
+    DeliverBackgroundReplies,
 }
 
 /// Information about server-side failure returned through RPC API
@@ -672,13 +1210,86 @@ pub struct Failure {
 )]
 #[display(Debug)]
 pub enum FailureCode {
+    /// The stored checkpoint of a swap is missing or cannot be decoded
+    CheckpointCorrupt = 0x001,
+
+    /// The timelocks of an offer are out of bounds or not ordered
+    InvalidTimelock = 0x002,
+
+    /// A swap already has the maximum number of progress subscribers
+    TooManySubscribers = 0x003,
+
+    /// The fee strategy of an offer cannot be evaluated by the node
+    UnsupportedFeeStrategy = 0x004,
+
+    /// The protocol version negotiated with the counterparty peer does not support swaps
+    IncompatibleProtocolVersion = 0x005,
+
+    /// The binary of a service farcasterd had to launch is missing or not executable
+    BinaryNotFound = 0x006,
+
+    /// A taker committed to an offer too close to its expiry
+    OfferExpiringSoon = 0x007,
+
+    /// The maker of an offer has no successful swap history with the node
+    UntrustedPeer = 0x008,
+
+    /// A request was received on another bus than the one it belongs to
+    WrongBus = 0x009,
+
+    /// A request needs a service farcasterd does not launch in safe mode
+    DisabledInSafeMode = 0x00A,
+
+    /// The node started too recently to accept new offers and swaps
+    NodeStabilizing = 0x00B,
+
+    /// The counterparty aborted the swap before it was locked
+    CounterpartyAborted = 0x00C,
+
     /// Catch-all: TODO: Expand
     Unknown = 0xFFF,
 }
 
+impl Failure {
+    /// Encodes the failure as the body of a peer abort message, e.g. `#7: <info>`, so the
+    /// counterparty learns the failure code along with the reason
+    pub fn to_abort_body(&self) -> String {
+        format!("#{}: {}", u16::from(self.code), self.info)
+    }
+
+    /// Decodes the body of a peer abort message, a body without a failure code, e.g. sent by a
+    /// node predating the encoding, is attributed to the given code
+    pub fn from_abort_body(body: &str, default: FailureCode) -> Failure {
+        body.strip_prefix('#')
+            .and_then(|body| body.split_once(": "))
+            .and_then(|(code, info)| {
+                code.parse::<u16>().ok().map(|code| Failure {
+                    code: code.into(),
+                    info: info.to_string(),
+                })
+            })
+            .unwrap_or_else(|| Failure {
+                code: default,
+                info: body.to_string(),
+            })
+    }
+}
+
 impl From<u16> for FailureCode {
     fn from(value: u16) -> Self {
         match value {
+            0x001 => FailureCode::CheckpointCorrupt,
+            0x002 => FailureCode::InvalidTimelock,
+            0x003 => FailureCode::TooManySubscribers,
+            0x004 => FailureCode::UnsupportedFeeStrategy,
+            0x005 => FailureCode::IncompatibleProtocolVersion,
+            0x006 => FailureCode::BinaryNotFound,
+            0x007 => FailureCode::OfferExpiringSoon,
+            0x008 => FailureCode::UntrustedPeer,
+            0x009 => FailureCode::WrongBus,
+            0x00A => FailureCode::DisabledInSafeMode,
+            0x00B => FailureCode::NodeStabilizing,
+            0x00C => FailureCode::CounterpartyAborted,
             _ => FailureCode::Unknown,
         }
     }
@@ -723,6 +1334,75 @@ pub enum OfferStatusSelector {
     All,
 }
 
+/// Direction of an offer seen from the taker, i.e. what the taker gets out of the swap
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Display, StrictEncode, StrictDecode)]
+pub enum OfferDirection {
+    /// The taker buys bitcoin with monero
+    #[display("buy-btc")]
+    BuyBitcoin,
+    /// The taker sells bitcoin for monero
+    #[display("sell-btc")]
+    SellBitcoin,
+}
+
+impl OfferDirection {
+    /// Returns the direction of the offer for its taker
+    pub fn of(offer: &PublicOffer) -> OfferDirection {
+        match offer.offer.maker_role.other() {
+            SwapRole::Alice => OfferDirection::BuyBitcoin,
+            SwapRole::Bob => OfferDirection::SellBitcoin,
+        }
+    }
+
+    pub fn matches(&self, offer: &PublicOffer) -> bool {
+        // Alice holds the monero, so a taker in the Alice role buys bitcoin
+        let taker_role = offer.offer.maker_role.other();
+        matches!(
+            (self, taker_role),
+            (OfferDirection::BuyBitcoin, SwapRole::Alice)
+                | (OfferDirection::SellBitcoin, SwapRole::Bob)
+        )
+    }
+}
+
+impl FromStr for OfferDirection {
+    type Err = String;
+    fn from_str(input: &str) -> Result<OfferDirection, Self::Err> {
+        match input {
+            "buy-btc" | "sell-xmr" => Ok(OfferDirection::BuyBitcoin),
+            "sell-btc" | "buy-xmr" => Ok(OfferDirection::SellBitcoin),
+            _ => Err(format!(
+                "Unknown offer direction {}, use buy-btc or sell-btc",
+                input
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Display, StrictEncode, StrictDecode)]
+#[display(Debug)]
+pub struct ListOffers {
+    pub selector: OfferStatusSelector,
+    /// Only list the offers in this direction, all offers if unset
+    pub direction: Option<OfferDirection>,
+}
+
+impl ListOffers {
+    pub fn matches(&self, offer: &PublicOffer) -> bool {
+        self.direction
+            .map_or(true, |direction| direction.matches(offer))
+    }
+}
+
+impl From<OfferStatusSelector> for ListOffers {
+    fn from(selector: OfferStatusSelector) -> ListOffers {
+        ListOffers {
+            selector,
+            direction: None,
+        }
+    }
+}
+
 impl From<OfferSelector> for OfferStatusSelector {
     fn from(offer_selector: OfferSelector) -> OfferStatusSelector {
         match offer_selector {
@@ -808,6 +1488,27 @@ pub struct CheckpointEntry {
     pub trade_role: TradeRole,
 }
 
+/// Result of databased checking the stored walletd and swapd checkpoints of a
+/// swap before it gets restored.
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+pub enum CheckpointValidation {
+    /// Both checkpoints decode; carries the entry recovered from the walletd checkpoint
+    #[display("valid({0})")]
+    Valid(CheckpointEntry),
+    /// A checkpoint is missing or corrupt; carries the reason
+    #[display("corrupt({0}, {1})")]
+    Corrupt(SwapId, String),
+}
+
+impl CheckpointValidation {
+    pub fn swap_id(&self) -> SwapId {
+        match self {
+            CheckpointValidation::Valid(CheckpointEntry { swap_id, .. }) => *swap_id,
+            CheckpointValidation::Corrupt(swap_id, _) => *swap_id,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Display, StrictDecode, StrictEncode)]
 #[display(Debug)]
 pub struct Checkpoint {
@@ -900,9 +1601,30 @@ pub struct MoneroFundingInfo {
     pub address: monero::Address,
 }
 
-impl rpc::Request for Request {}
+/// Every pending funding need of the running swaps, on both blockchains
+#[derive(Clone, Debug, StrictEncode, StrictDecode)]
+pub struct FundingNeeds {
+    pub bitcoin: Vec<BitcoinFundingInfo>,
+    pub monero: Vec<MoneroFundingInfo>,
+}
 
-#[derive(Clone, Debug, Display, StrictEncode, StrictDecode)]
+impl fmt::Display for FundingNeeds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Bitcoin:")?;
+        for funding_info in &self.bitcoin {
+            writeln!(f, "  {}", funding_info)?;
+        }
+        writeln!(f, "Monero:")?;
+        for funding_info in &self.monero {
+            writeln!(f, "  {}", funding_info)?;
+        }
+        Ok(())
+    }
+}
+
+impl rpc::Request for Request {}
+
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode)]
 #[display("{source}, {event}")]
 pub struct SyncerdBridgeEvent {
     pub event: Event,
@@ -936,6 +1658,8 @@ pub enum Progress {
 )]
 #[display(NodeInfo::to_yaml_string)]
 pub struct NodeInfo {
+    pub node_id: Option<internet2::addr::NodeId>,
+    pub alias: Option<String>,
     pub listens: Vec<InetSocketAddr>,
     #[serde_as(as = "DurationSeconds")]
     pub uptime: Duration,
@@ -945,6 +1669,10 @@ pub struct NodeInfo {
     pub swaps: Vec<SwapId>,
     #[serde_as(as = "Vec<DisplayFromStr>")]
     pub offers: Vec<PublicOffer>,
+    pub auto_funding: bool,
+    /// Set if the system time differs from the time of the latest blocks by more than the
+    /// configured threshold, time-derived estimates may then be wrong
+    pub clock_skewed: bool,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
@@ -963,6 +1691,13 @@ pub struct ProtoPublicOffer {
     pub bind_addr: InetSocketAddr,
     pub arbitrating_addr: bitcoin::Address,
     pub accordant_addr: monero::Address,
+    /// Set if the offer fee strategy was explicitly given by the user, otherwise farcasterd
+    /// resolves it from the configuration
+    pub explicit_fee_strategy: bool,
+    /// Syncer servers of the swaps resulting from this offer
+    pub syncer_servers: SyncerServersOverride,
+    /// Lifetime of the offer, it is revoked once expired if no taker committed to it
+    pub expiry: Option<Duration>,
 }
 
 #[cfg_attr(feature = "serde", serde_as)]
@@ -989,6 +1724,66 @@ pub struct MadeOffer {
 pub struct OfferInfo {
     pub offer: String,
     pub details: PublicOffer,
+    /// Time left before the offer expires, unset for offers without expiry
+    #[serde_as(as = "Option<DurationSeconds>")]
+    pub ttl: Option<Duration>,
+}
+
+/// An offer of a maker node known to this node, with its status: open, in progress, or ended
+/// once neither open nor consumed by a running swap
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(MakerOffer::to_yaml_string)]
+pub struct MakerOffer {
+    pub status: String,
+    pub info: OfferInfo,
+}
+
+/// The open offers with everything needed to publish them, e.g. on an order book page. The
+/// time to live of the offers is relative to the assembly time, so the document can be cached.
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(OfferBook::to_yaml_string)]
+pub struct OfferBook {
+    /// Time the offer book was assembled at, in seconds since the unix epoch
+    pub generated_at: u64,
+    pub offers: Vec<OfferBookEntry>,
+}
+
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(OfferBookEntry::to_yaml_string)]
+pub struct OfferBookEntry {
+    pub offer_id: String,
+    /// The encoded public offer, to be given to the taker
+    pub offer: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub network: Network,
+    #[serde_as(as = "DisplayFromStr")]
+    pub direction: OfferDirection,
+    #[serde_as(as = "DisplayFromStr")]
+    pub arbitrating_amount: bitcoin::Amount,
+    #[serde_as(as = "DisplayFromStr")]
+    pub accordant_amount: monero::Amount,
+    #[serde_as(as = "DisplayFromStr")]
+    pub maker_node_id: bitcoin::secp256k1::PublicKey,
+    /// Time left before the offer expires, unset for offers without expiry
+    #[serde_as(as = "Option<DurationSeconds>")]
+    pub ttl: Option<Duration>,
 }
 
 #[cfg_attr(feature = "serde", serde_as)]
@@ -1020,6 +1815,103 @@ impl StrictDecode for TookOffer {
     }
 }
 
+/// A swap, offer or peer lifecycle event, sent to the clients subscribed to the node events
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+pub enum NodeEvent {
+    #[display("offer_made({0})")]
+    OfferMade(PublicOffer),
+    #[display("offer_revoked({0})")]
+    OfferRevoked(PublicOffer),
+    #[display("swap_started({0})")]
+    SwapStarted(SwapId),
+    #[display("swap_ended({0}, {1})")]
+    SwapEnded(SwapId, Outcome),
+    #[display("peer_connected({0})")]
+    PeerConnected(NodeAddr),
+    #[display("peer_disconnected({0})")]
+    PeerDisconnected(NodeAddr),
+}
+
+/// Sent to the client that made or took an offer once the resulting swap is launched, to
+/// correlate the offer with its swap
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SwapStarted::to_yaml_string)]
+pub struct SwapStarted {
+    pub offer_id: Uuid,
+    #[serde_as(as = "DisplayFromStr")]
+    pub swap_id: SwapId,
+}
+
+impl StrictEncode for SwapStarted {
+    fn strict_encode<W: std::io::Write>(&self, mut w: W) -> Result<usize, strict_encoding::Error> {
+        let mut len = self.offer_id.to_bytes_le().strict_encode(&mut w)?;
+        len += self.swap_id.strict_encode(&mut w)?;
+        Ok(len)
+    }
+}
+
+impl StrictDecode for SwapStarted {
+    fn strict_decode<R: std::io::Read>(mut r: R) -> Result<Self, strict_encoding::Error> {
+        let offer_id = Uuid::from_bytes_le(<[u8; 16]>::strict_decode(&mut r)?);
+        let swap_id = SwapId::strict_decode(&mut r)?;
+        Ok(SwapStarted { offer_id, swap_id })
+    }
+}
+
+/// Asks if an offer was ever known to the node, open, consumed or ended
+#[derive(Clone, PartialEq, Eq, Debug, Display)]
+#[display("{offer_id}")]
+pub struct HasSeenOffer {
+    pub offer_id: Uuid,
+}
+
+impl StrictEncode for HasSeenOffer {
+    fn strict_encode<W: std::io::Write>(&self, mut w: W) -> Result<usize, strict_encoding::Error> {
+        self.offer_id.to_bytes_le().strict_encode(&mut w)
+    }
+}
+
+impl StrictDecode for HasSeenOffer {
+    fn strict_decode<R: std::io::Read>(mut r: R) -> Result<Self, strict_encoding::Error> {
+        let offer_id = Uuid::from_bytes_le(<[u8; 16]>::strict_decode(&mut r)?);
+        Ok(HasSeenOffer { offer_id })
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(OfferSeen::to_yaml_string)]
+pub struct OfferSeen {
+    pub offer_id: Uuid,
+    pub seen: bool,
+}
+
+impl StrictEncode for OfferSeen {
+    fn strict_encode<W: std::io::Write>(&self, mut w: W) -> Result<usize, strict_encoding::Error> {
+        let mut len = self.offer_id.to_bytes_le().strict_encode(&mut w)?;
+        len += self.seen.strict_encode(&mut w)?;
+        Ok(len)
+    }
+}
+
+impl StrictDecode for OfferSeen {
+    fn strict_decode<R: std::io::Read>(mut r: R) -> Result<Self, strict_encoding::Error> {
+        let offer_id = Uuid::from_bytes_le(<[u8; 16]>::strict_decode(&mut r)?);
+        let seen = bool::strict_decode(&mut r)?;
+        Ok(OfferSeen { offer_id, seen })
+    }
+}
+
 #[cfg_attr(feature = "serde", serde_as)]
 #[derive(Clone, PartialEq, Eq, Debug, Display, Default, StrictEncode, StrictDecode)]
 #[cfg_attr(
@@ -1030,6 +1922,12 @@ impl StrictDecode for TookOffer {
 #[display(SwapProgress::to_yaml_string)]
 pub struct SwapProgress {
     pub progress: Vec<ProgressEvent>,
+    /// The unix time at which each event was queued, empty if the times are not known
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub timestamps: Vec<u64>,
 }
 #[cfg_attr(feature = "serde", serde_as)]
 #[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
@@ -1050,6 +1948,26 @@ pub enum ProgressEvent {
     Failure(Failure),
 }
 
+/// The syncer servers the node uses for a network, with the credentials in their urls redacted
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SyncerServersInfo::to_yaml_string)]
+pub struct SyncerServersInfo {
+    #[serde_as(as = "DisplayFromStr")]
+    pub network: Network,
+    pub electrum_server: String,
+    pub monero_daemon: String,
+    pub monero_rpc_wallet: String,
+    pub monero_lws: Option<String>,
+    pub monero_wallet_dir: Option<String>,
+    pub instances: Vec<String>,
+}
+
 #[cfg_attr(feature = "serde", serde_as)]
 #[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
 #[cfg_attr(
@@ -1064,8 +1982,10 @@ pub struct SyncerInfo {
     pub since: u64,
     #[serde_as(as = "Vec<DisplayFromStr>")]
     pub tasks: Vec<u64>,
+    pub paused: bool,
 }
 
+/// Whether an address takers use to reach a listener accepted a connection from the node itself
 #[cfg_attr(feature = "serde", serde_as)]
 #[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
 #[cfg_attr(
@@ -1073,138 +1993,1025 @@ pub struct SyncerInfo {
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate")
 )]
-#[display(PeerInfo::to_yaml_string)]
-pub struct PeerInfo {
-    pub local_id: internet2::addr::NodeId,
-    pub remote_id: Vec<internet2::addr::NodeId>,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    pub local_socket: Option<InetSocketAddr>,
-    #[serde_as(as = "Vec<DisplayFromStr>")]
-    pub remote_socket: Vec<InetSocketAddr>,
-    #[serde_as(as = "DurationSeconds")]
-    pub uptime: Duration,
-    pub since: u64,
-    pub messages_sent: usize,
-    pub messages_received: usize,
-    pub forked_from_listener: bool,
-    pub awaits_pong: bool,
+#[display(ListenReachability::to_yaml_string)]
+pub struct ListenReachability {
+    #[serde_as(as = "DisplayFromStr")]
+    pub listen: InetSocketAddr,
+    #[serde_as(as = "DisplayFromStr")]
+    pub address: InetSocketAddr,
+    pub reachable: bool,
+    pub error: Option<String>,
 }
-pub type RemotePeerMap<T> = BTreeMap<NodeAddr, T>;
+
+/// A peer connection and the protocol version negotiated over it, if already known
 #[cfg_attr(feature = "serde", serde_as)]
-#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate")
 )]
-#[display(SwapInfo::to_yaml_string)]
-pub struct SwapInfo {
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    pub swap_id: Option<SwapId>,
-    // pub state: crate::swapd::State,
+#[display(ConnectedPeer::to_yaml_string)]
+pub struct ConnectedPeer {
+    #[serde_as(as = "DisplayFromStr")]
+    pub peer: NodeAddr,
+    pub protocol_version: Option<u16>,
+}
+
+/// The clients subscribed to the progress of a swap, the batched ones receive the progress
+/// coalesced instead of event by event
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(ProgressSubscriptions::to_yaml_string)]
+pub struct ProgressSubscriptions {
+    #[serde_as(as = "DisplayFromStr")]
+    pub swap_id: SwapId,
     #[serde_as(as = "Vec<DisplayFromStr>")]
-    pub maker_peer: Vec<NodeAddr>,
-    #[serde_as(as = "DurationSeconds")]
-    pub uptime: Duration,
-    pub since: u64,
-    pub public_offer: PublicOffer,
+    pub clients: Vec<ServiceId>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub batched: Vec<ServiceId>,
 }
 
-#[cfg(feature = "serde")]
-impl ToYamlString for NodeInfo {}
-#[cfg(feature = "serde")]
-impl ToYamlString for PeerInfo {}
-#[cfg(feature = "serde")]
-impl ToYamlString for SwapInfo {}
-#[cfg(feature = "serde")]
-impl ToYamlString for SyncerInfo {}
-#[cfg(feature = "serde")]
-impl ToYamlString for MadeOffer {}
-#[cfg(feature = "serde")]
-impl ToYamlString for TookOffer {}
-#[cfg(feature = "serde")]
-impl ToYamlString for SwapProgress {}
-#[cfg(feature = "serde")]
-impl ToYamlString for ProgressEvent {}
-#[cfg(feature = "serde")]
-impl ToYamlString for CheckpointEntry {}
-#[cfg(feature = "serde")]
-impl ToYamlString for OfferStatusPair {}
-#[cfg(feature = "serde")]
-impl ToYamlString for OfferInfo {}
+/// Number of swaps served by a syncer
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SyncerClients::to_yaml_string)]
+pub struct SyncerClients {
+    #[serde_as(as = "DisplayFromStr")]
+    pub syncer: ServiceId,
+    pub swaps: u64,
+}
 
-#[derive(Wrapper, Clone, PartialEq, Eq, Debug, From, StrictEncode, StrictDecode)]
-#[wrapper(IndexRange)]
-pub struct List<T>(Vec<T>)
-where
-    T: Clone + PartialEq + Eq + Debug + Display + StrictEncode + StrictDecode;
+/// The outcomes of the swaps run with a peer, the counterparty node of a swap is the maker node
+/// of its offer for takers and the connected taker node for makers
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(PeerReputation::to_yaml_string)]
+pub struct PeerReputation {
+    #[serde_as(as = "DisplayFromStr")]
+    pub node_id: bitcoin::secp256k1::PublicKey,
+    pub success: u64,
+    pub refund: u64,
+    pub punish: u64,
+    pub abort: u64,
+}
+
+impl PeerReputation {
+    pub fn new(node_id: bitcoin::secp256k1::PublicKey) -> Self {
+        PeerReputation {
+            node_id,
+            success: 0,
+            refund: 0,
+            punish: 0,
+            abort: 0,
+        }
+    }
 
-#[cfg(feature = "serde")]
-impl<T> Display for List<T>
-where
-    T: Clone + PartialEq + Eq + Debug + Display + serde::Serialize + StrictEncode + StrictDecode,
-{
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.write_str(&serde_yaml::to_string(self).expect("internal YAML serialization error"))
+    pub fn incr_outcome(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Buy => self.success += 1,
+            Outcome::Refund => self.refund += 1,
+            Outcome::Punish => self.punish += 1,
+            Outcome::Abort => self.abort += 1,
+        };
     }
-}
 
-impl<T> FromIterator<T> for List<T>
-where
-    T: Clone + PartialEq + Eq + Debug + Display + serde::Serialize + StrictEncode + StrictDecode,
-{
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        Self::from_inner(iter.into_iter().collect())
+    /// A peer is trusted once it completed more swaps than it ended in a refund or a punish,
+    /// aborted swaps did not lock funds and are not held against it
+    pub fn is_trusted(&self) -> bool {
+        self.success > self.refund + self.punish
     }
 }
 
-#[cfg(feature = "serde")]
-impl<T> serde::Serialize for List<T>
-where
-    T: Clone + PartialEq + Eq + Debug + Display + serde::Serialize + StrictEncode + StrictDecode,
-{
-    fn serialize<S>(
-        &self,
-        serializer: S,
-    ) -> Result<<S as serde::Serializer>::Ok, <S as serde::Serializer>::Error>
-    where
-        S: serde::Serializer,
-    {
-        self.as_inner().serialize(serializer)
+/// The times, in seconds since the unix epoch, a swap reached its milestones. Only the funding
+/// of the local side is known, and the swap is locked once its arbitrating lock transaction is
+/// reported. Milestones not reached yet are none.
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SwapTimeline::to_yaml_string)]
+pub struct SwapTimeline {
+    #[serde_as(as = "DisplayFromStr")]
+    pub swap_id: SwapId,
+    pub initiated: u64,
+    pub funded_btc: Option<u64>,
+    pub funded_xmr: Option<u64>,
+    pub locked: Option<u64>,
+    pub ended: Option<u64>,
+    pub outcome: Option<Outcome>,
+}
+
+impl SwapTimeline {
+    pub fn new(swap_id: SwapId, initiated: u64) -> Self {
+        SwapTimeline {
+            swap_id,
+            initiated,
+            funded_btc: None,
+            funded_xmr: None,
+            locked: None,
+            ended: None,
+            outcome: None,
+        }
     }
 }
 
-#[derive(Wrapper, Clone, PartialEq, Eq, Debug, From, Default, StrictEncode, StrictDecode)]
+/// The current state of a swap and how long it has been in it
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate")
 )]
-pub struct OptionDetails(pub Option<String>);
+#[display(SwapStateDwell::to_yaml_string)]
+pub struct SwapStateDwell {
+    #[serde_as(as = "DisplayFromStr")]
+    pub swap_id: SwapId,
+    pub state: String,
+    /// Time the swap entered the state, in seconds since the unix epoch
+    pub entered: u64,
+    #[serde_as(as = "DurationSeconds")]
+    pub dwell: Duration,
+}
 
-impl Display for OptionDetails {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self.as_inner() {
-            None => Ok(()),
-            Some(msg) => f.write_str(msg),
-        }
-    }
+/// The effective parameters farcasterd was launched with
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(StartupInfo::to_yaml_string)]
+pub struct StartupInfo {
+    pub version: String,
+    pub data_dir: String,
+    pub msg_socket: String,
+    pub ctl_socket: String,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub tor_proxy: Option<InetSocketAddr>,
+    pub safe_mode: bool,
+    pub grpc_enabled: bool,
+    pub auto_funding_enabled: bool,
+    /// Networks with syncer servers configured
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub syncer_networks: Vec<Network>,
 }
 
-impl OptionDetails {
-    pub fn with(s: impl ToString) -> Self {
-        Self(Some(s.to_string()))
-    }
+/// The outcome of running a service binary with `--version`, without integrating it into the node
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(TestLaunch::to_yaml_string)]
+pub struct TestLaunch {
+    pub service: String,
+    pub runnable: bool,
+    /// None if the binary could not be launched or did not exit in time
+    pub exit_code: Option<i32>,
+    /// The output of the binary, or why it could not be launched
+    pub output: String,
+}
 
-    pub fn new() -> Self {
-        Self(None)
+/// The version of the running node and how it was built
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(VersionInfo::to_yaml_string)]
+pub struct VersionInfo {
+    pub version: String,
+    /// The commit the node was built from, if built from a git checkout
+    pub git_commit: Option<String>,
+    pub farcaster_core_version: String,
+}
+
+impl VersionInfo {
+    /// The version info embedded in this build
+    pub fn current() -> Self {
+        VersionInfo {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: option_env!("FARCASTER_GIT_COMMIT").map(|commit| commit.to_string()),
+            farcaster_core_version: option_env!("FARCASTER_CORE_VERSION")
+                .unwrap_or("unknown")
+                .to_string(),
+        }
+    }
+}
+
+/// The restores cancelled before their swapd received the checkpoint, and the ones whose swapd
+/// already did and cannot be cancelled anymore
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(CancelledRestores::to_yaml_string)]
+pub struct CancelledRestores {
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub cancelled: Vec<SwapId>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub uncancellable: Vec<SwapId>,
+}
+
+/// The timelocks of a swap, in blocks, and the Bitcoin heights from which they are estimated to
+/// be valid once the lock transaction is mined
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SwapTimelocks::to_yaml_string)]
+pub struct SwapTimelocks {
+    #[serde_as(as = "DisplayFromStr")]
+    pub swap_id: SwapId,
+    pub cancel_timelock: u32,
+    pub punish_timelock: u32,
+    /// The current Bitcoin height seen by the syncer, none if not known yet
+    pub current_height: Option<u64>,
+    /// The height the lock transaction was mined at, none if not mined yet
+    pub lock_height: Option<u64>,
+    /// The height from which the cancel transaction is valid
+    pub cancel_valid_height: Option<u64>,
+    /// The height from which the punish transaction is valid, the earliest possible one while
+    /// the cancel transaction is not mined
+    pub punish_valid_height: Option<u64>,
+}
+
+/// The peer connections that answered a refresh, and the ones removed as they did not answer in
+/// time
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(ConnectionsRefreshed::to_yaml_string)]
+pub struct ConnectionsRefreshed {
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub responsive: Vec<NodeAddr>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub removed: Vec<NodeAddr>,
+}
+
+/// The syncers affected by a reload of the syncer servers of a network. The idle syncers are
+/// terminated right away, the busy ones are queued and terminated once they become idle. Either
+/// way they are only relaunched with the new servers on their next use.
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SyncerServersReload::to_yaml_string)]
+pub struct SyncerServersReload {
+    #[serde_as(as = "DisplayFromStr")]
+    pub network: Network,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub terminated: Vec<ServiceId>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub queued: Vec<ServiceId>,
+}
+
+/// A transaction of a swap, reported by swapd once broadcast or observed on-chain
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SwapTxid::to_yaml_string)]
+pub struct SwapTxid {
+    pub tx_label: TxLabel,
+    #[serde_as(as = "DisplayFromStr")]
+    pub txid: Txid,
+    /// Link to the transaction on the block explorer of the swap network, only set by farcasterd
+    pub explorer_url: Option<String>,
+}
+
+/// What revoking an offer would affect. An offer a taker already committed to is consumed by
+/// its swap and can no longer be revoked.
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(RevokeImpact::to_yaml_string)]
+pub struct RevokeImpact {
+    #[serde_as(as = "DisplayFromStr")]
+    pub public_offer: PublicOffer,
+    pub revocable: bool,
+    pub taker_committed: bool,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub swap_id: Option<SwapId>,
+    /// Listener left without any open offer once the offer is revoked
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub idle_listener: Option<InetSocketAddr>,
+}
+
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(PeerInfo::to_yaml_string)]
+pub struct PeerInfo {
+    pub local_id: internet2::addr::NodeId,
+    pub remote_id: Vec<internet2::addr::NodeId>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub local_socket: Option<InetSocketAddr>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub remote_socket: Vec<InetSocketAddr>,
+    #[serde_as(as = "DurationSeconds")]
+    pub uptime: Duration,
+    pub since: u64,
+    pub messages_sent: usize,
+    pub messages_received: usize,
+    pub forked_from_listener: bool,
+    pub awaits_pong: bool,
+    pub protocol_version: Option<u16>,
+}
+pub type RemotePeerMap<T> = BTreeMap<NodeAddr, T>;
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SwapInfo::to_yaml_string)]
+pub struct SwapInfo {
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub swap_id: Option<SwapId>,
+    // pub state: crate::swapd::State,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub maker_peer: Vec<NodeAddr>,
+    #[serde_as(as = "DurationSeconds")]
+    pub uptime: Duration,
+    pub since: u64,
+    pub public_offer: PublicOffer,
+    /// Only tracked by swapd, unset when the info is assembled by farcasterd
+    pub message_stats: Option<MessageStats>,
+}
+
+/// Number and total encoded size of the protocol messages a swapd exchanged with its peer
+#[derive(Clone, Copy, Debug, Default, Display, StrictEncode, StrictDecode, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(
+    "sent {messages_sent} ({bytes_sent} B), received {messages_received} ({bytes_received} B)"
+)]
+pub struct MessageStats {
+    pub messages_sent: u64,
+    pub bytes_sent: u64,
+    pub messages_received: u64,
+    pub bytes_received: u64,
+}
+
+impl MessageStats {
+    pub fn record_sent(&mut self, bytes: usize) {
+        self.messages_sent += 1;
+        self.bytes_sent += bytes as u64;
+    }
+
+    pub fn record_received(&mut self, bytes: usize) {
+        self.messages_received += 1;
+        self.bytes_received += bytes as u64;
+    }
+
+    pub fn add(&mut self, other: &MessageStats) {
+        self.messages_sent += other.messages_sent;
+        self.bytes_sent += other.bytes_sent;
+        self.messages_received += other.messages_received;
+        self.bytes_received += other.bytes_received;
+    }
+}
+
+/// Asks for the requests farcasterd received but did not process, optionally forgetting them
+/// once reported
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[display("clear: {clear}")]
+pub struct GetUnprocessedRequests {
+    pub clear: bool,
+}
+
+/// A request matching no state machine, dropped by farcasterd
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display("{request} from {source} at {time}")]
+pub struct UnprocessedRequest {
+    /// The type of the request, its content is not kept
+    pub request: String,
+    pub source: String,
+    /// Seconds since the unix epoch
+    pub time: u64,
+}
+
+/// The most recent unprocessed requests, the oldest first
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(UnprocessedRequests::to_yaml_string)]
+pub struct UnprocessedRequests {
+    pub requests: Vec<UnprocessedRequest>,
+}
+
+/// Number of requests a process sent and received on a bus
+#[derive(Clone, Copy, Debug, Default, Display, StrictEncode, StrictDecode, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display("sent {sent}, received {received}")]
+pub struct BusMessageCount {
+    pub sent: u64,
+    pub received: u64,
+}
+
+/// Cumulative traffic of the node since startup
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(ThroughputStats::to_yaml_string)]
+pub struct ThroughputStats {
+    /// Protocol messages exchanged with peers, as reported by the swaps when they end
+    pub peer_messages: MessageStats,
+    /// Requests farcasterd sent and received on each bus
+    pub msg_bus: BusMessageCount,
+    pub ctl_bus: BusMessageCount,
+    pub bridge_bus: BusMessageCount,
+}
+
+/// The coordination state farcasterd keeps about a swap, for debugging purposes. Fields not
+/// tracked in the current state are left empty, no key material is ever part of the dump.
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(TradeStateDump::to_yaml_string)]
+pub struct TradeStateDump {
+    #[serde_as(as = "DisplayFromStr")]
+    pub swap_id: SwapId,
+    pub state: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub public_offer: PublicOffer,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub trade_role: Option<TradeRole>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub connection: Option<ServiceId>,
+    pub connected: Option<bool>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub arbitrating_syncer: Option<ServiceId>,
+    pub arbitrating_syncer_up: bool,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub accordant_syncer: Option<ServiceId>,
+    pub accordant_syncer_up: bool,
+    pub swapd_up: bool,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub funding_address: Option<bitcoin::Address>,
+    pub funding_info: Option<String>,
+    pub auto_funded: Option<bool>,
+    pub funds_locked: bool,
+    pub reconnect_attempts: Option<u32>,
+}
+
+/// The services farcasterd knows about: the registered ones said hello, the spawning ones were
+/// launched but did not say hello yet
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(ServiceInventoryReport::to_yaml_string)]
+pub struct ServiceInventoryReport {
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub registered: Vec<ServiceId>,
+    pub spawning: Vec<SpawningService>,
+}
+
+/// The funds the local side of the running swaps locked, summed per blockchain
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(ValueLocked::to_yaml_string)]
+pub struct ValueLocked {
+    #[serde_as(as = "DisplayFromStr")]
+    pub bitcoin: bitcoin::Amount,
+    #[serde_as(as = "DisplayFromStr")]
+    pub monero: monero::Amount,
+    pub funded_swaps: u64,
+}
+
+/// Why a swap stopped awaiting its funding
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum FundingCancelReason {
+    /// The user aborted the swap
+    #[display("user_aborted")]
+    UserAborted,
+    /// The swap got too close to its cancel timelock, or passed it, before being funded
+    #[display("timeout")]
+    Timeout,
+    /// The swap was aborted on behalf of the counterparty
+    #[display("counterparty_failed")]
+    CounterpartyFailed,
+    /// The funding received fell short of the required amount
+    #[display("insufficient_balance")]
+    InsufficientBalance,
+}
+
+/// Number of funding cancellations of a blockchain, per reason
+#[derive(Clone, Copy, Debug, Default, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(FundingCancellations::to_yaml_string)]
+pub struct FundingCancellations {
+    pub user_aborted: u64,
+    pub timeout: u64,
+    pub counterparty_failed: u64,
+    pub insufficient_balance: u64,
+}
+
+impl FundingCancellations {
+    pub fn record(&mut self, reason: FundingCancelReason) {
+        match reason {
+            FundingCancelReason::UserAborted => self.user_aborted += 1,
+            FundingCancelReason::Timeout => self.timeout += 1,
+            FundingCancelReason::CounterpartyFailed => self.counterparty_failed += 1,
+            FundingCancelReason::InsufficientBalance => self.insufficient_balance += 1,
+        }
+    }
+}
+
+/// The funding cancellations since farcasterd started, per blockchain and reason
+#[derive(Clone, Copy, Debug, Default, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(FundingCancellationBreakdown::to_yaml_string)]
+pub struct FundingCancellationBreakdown {
+    pub bitcoin: FundingCancellations,
+    pub monero: FundingCancellations,
+}
+
+impl FundingCancellationBreakdown {
+    pub fn record(&mut self, blockchain: &Blockchain, reason: FundingCancelReason) {
+        match blockchain {
+            Blockchain::Bitcoin => self.bitcoin.record(reason),
+            Blockchain::Monero => self.monero.record(reason),
+        }
+    }
+}
+
+/// The problems found in the configuration of the node, which break some functionality, and
+/// the warnings, which are likely unintended
+#[derive(Clone, Debug, Default, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(ConfigValidation::to_yaml_string)]
+pub struct ConfigValidation {
+    pub problems: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ConfigValidation {
+    pub fn problem(&mut self, problem: String) {
+        self.problems.push(problem);
+    }
+
+    pub fn warning(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+}
+
+/// The counterparty node of a swap, as opposed to the connection the swap currently runs over,
+/// along with the outcomes of the swaps run with it. The node id is unknown for restored maker
+/// swaps.
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, Display, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SwapCounterparty::to_yaml_string)]
+pub struct SwapCounterparty {
+    #[serde_as(as = "DisplayFromStr")]
+    pub swap_id: SwapId,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub node_id: Option<bitcoin::secp256k1::PublicKey>,
+    pub reputation: Option<PeerReputation>,
+}
+
+/// The distinct networks of the running swaps and open offers of the node
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, Default, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(ActiveNetworks::to_yaml_string)]
+pub struct ActiveNetworks {
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub networks: Vec<Network>,
+}
+
+/// How many swaps are in each trade state machine state, keyed by the state display name
+#[derive(Clone, Debug, Default, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SwapStateHistogram::to_yaml_string)]
+pub struct SwapStateHistogram {
+    pub states: BTreeMap<String, u32>,
+}
+
+/// The data directory and bus sockets farcasterd launches the services with, and the grpc
+/// endpoint if grpcd is run
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(NodePaths::to_yaml_string)]
+pub struct NodePaths {
+    pub data_dir: String,
+    pub msg_socket: String,
+    pub ctl_socket: String,
+    pub grpc_endpoint: Option<String>,
+}
+
+/// The swaps relying on a service, and if they would survive its termination
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(TeardownPreview::to_yaml_string)]
+pub struct TeardownPreview {
+    #[serde_as(as = "DisplayFromStr")]
+    pub service: ServiceId,
+    pub affected: Vec<TeardownImpact>,
+}
+
+/// A swap, or a take not launched as a swap yet, relying on a service
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(TeardownImpact::to_yaml_string)]
+pub struct TeardownImpact {
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub swap_id: Option<SwapId>,
+    pub offer_id: String,
+    /// False if the swap would be stranded
+    pub survives: bool,
+    pub reason: String,
+}
+
+/// A launched service that did not say hello yet
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SpawningService::to_yaml_string)]
+pub struct SpawningService {
+    #[serde_as(as = "DisplayFromStr")]
+    pub service: ServiceId,
+    pub since: u64,
+}
+
+/// The trade and syncer state machines farcasterd currently runs
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(StateMachines::to_yaml_string)]
+pub struct StateMachines {
+    pub trade: Vec<TradeStateMachineInfo>,
+    pub syncer: Vec<SyncerStateMachineInfo>,
+}
+
+/// A trade state machine, identified by its swap once launched, by its offer before that
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, Display, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(TradeStateMachineInfo::to_yaml_string)]
+pub struct TradeStateMachineInfo {
+    pub state: String,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub swap_id: Option<SwapId>,
+    pub offer_id: Option<Uuid>,
+}
+
+impl StrictEncode for TradeStateMachineInfo {
+    fn strict_encode<W: std::io::Write>(&self, mut w: W) -> Result<usize, strict_encoding::Error> {
+        let mut len = self.state.strict_encode(&mut w)?;
+        len += self.swap_id.strict_encode(&mut w)?;
+        len += self
+            .offer_id
+            .map(|offer_id| offer_id.to_bytes_le())
+            .strict_encode(&mut w)?;
+        Ok(len)
+    }
+}
+
+impl StrictDecode for TradeStateMachineInfo {
+    fn strict_decode<R: std::io::Read>(mut r: R) -> Result<Self, strict_encoding::Error> {
+        let state = String::strict_decode(&mut r)?;
+        let swap_id = Option::<SwapId>::strict_decode(&mut r)?;
+        let offer_id = Option::<[u8; 16]>::strict_decode(&mut r)?.map(Uuid::from_bytes_le);
+        Ok(TradeStateMachineInfo {
+            state,
+            swap_id,
+            offer_id,
+        })
+    }
+}
+
+/// A syncer state machine, identified by the syncer task it awaits the result of
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+#[display(SyncerStateMachineInfo::to_yaml_string)]
+pub struct SyncerStateMachineInfo {
+    pub task_id: u32,
+    pub state: String,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub syncer: Option<ServiceId>,
+}
+
+#[cfg(feature = "serde")]
+impl ToYamlString for NodeInfo {}
+#[cfg(feature = "serde")]
+impl ToYamlString for ServiceInventoryReport {}
+#[cfg(feature = "serde")]
+impl ToYamlString for ValueLocked {}
+#[cfg(feature = "serde")]
+impl ToYamlString for FundingCancellations {}
+#[cfg(feature = "serde")]
+impl ToYamlString for ConfigValidation {}
+#[cfg(feature = "serde")]
+impl ToYamlString for ActiveNetworks {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SwapCounterparty {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SwapStateHistogram {}
+#[cfg(feature = "serde")]
+impl ToYamlString for NodePaths {}
+#[cfg(feature = "serde")]
+impl ToYamlString for OfferSeen {}
+#[cfg(feature = "serde")]
+impl ToYamlString for ThroughputStats {}
+#[cfg(feature = "serde")]
+impl ToYamlString for UnprocessedRequests {}
+#[cfg(feature = "serde")]
+impl ToYamlString for MakerOffer {}
+#[cfg(feature = "serde")]
+impl ToYamlString for TeardownPreview {}
+#[cfg(feature = "serde")]
+impl ToYamlString for TeardownImpact {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SwapStateDwell {}
+#[cfg(feature = "serde")]
+impl ToYamlString for StartupInfo {}
+#[cfg(feature = "serde")]
+impl ToYamlString for TestLaunch {}
+#[cfg(feature = "serde")]
+impl ToYamlString for VersionInfo {}
+#[cfg(feature = "serde")]
+impl ToYamlString for CancelledRestores {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SwapTimelocks {}
+#[cfg(feature = "serde")]
+impl ToYamlString for ConnectionsRefreshed {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SyncerServersReload {}
+#[cfg(feature = "serde")]
+impl ToYamlString for ProgressSubscriptions {}
+#[cfg(feature = "serde")]
+impl ToYamlString for FundingCancellationBreakdown {}
+#[cfg(feature = "serde")]
+impl ToYamlString for PeerReputation {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SwapTimeline {}
+#[cfg(feature = "serde")]
+impl ToYamlString for OfferBook {}
+#[cfg(feature = "serde")]
+impl ToYamlString for OfferBookEntry {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SpawningService {}
+#[cfg(feature = "serde")]
+impl ToYamlString for StateMachines {}
+#[cfg(feature = "serde")]
+impl ToYamlString for TradeStateMachineInfo {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SyncerStateMachineInfo {}
+#[cfg(feature = "serde")]
+impl ToYamlString for TradeStateDump {}
+#[cfg(feature = "serde")]
+impl ToYamlString for PeerInfo {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SwapInfo {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SyncerInfo {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SyncerServersInfo {}
+#[cfg(feature = "serde")]
+impl ToYamlString for MadeOffer {}
+#[cfg(feature = "serde")]
+impl ToYamlString for TookOffer {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SwapStarted {}
+#[cfg(feature = "serde")]
+impl ToYamlString for ListenReachability {}
+#[cfg(feature = "serde")]
+impl ToYamlString for RevokeImpact {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SwapTxid {}
+#[cfg(feature = "serde")]
+impl ToYamlString for ConnectedPeer {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SyncerClients {}
+#[cfg(feature = "serde")]
+impl ToYamlString for SwapProgress {}
+#[cfg(feature = "serde")]
+impl ToYamlString for ProgressEvent {}
+#[cfg(feature = "serde")]
+impl ToYamlString for CheckpointEntry {}
+#[cfg(feature = "serde")]
+impl ToYamlString for OfferStatusPair {}
+#[cfg(feature = "serde")]
+impl ToYamlString for OfferInfo {}
+
+#[derive(Wrapper, Clone, PartialEq, Eq, Debug, From, StrictEncode, StrictDecode)]
+#[wrapper(IndexRange)]
+pub struct List<T>(Vec<T>)
+where
+    T: Clone + PartialEq + Eq + Debug + Display + StrictEncode + StrictDecode;
+
+#[cfg(feature = "serde")]
+impl<T> Display for List<T>
+where
+    T: Clone + PartialEq + Eq + Debug + Display + serde::Serialize + StrictEncode + StrictDecode,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&serde_yaml::to_string(self).expect("internal YAML serialization error"))
+    }
+}
+
+impl<T> FromIterator<T> for List<T>
+where
+    T: Clone + PartialEq + Eq + Debug + Display + serde::Serialize + StrictEncode + StrictDecode,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_inner(iter.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for List<T>
+where
+    T: Clone + PartialEq + Eq + Debug + Display + serde::Serialize + StrictEncode + StrictDecode,
+{
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> Result<<S as serde::Serializer>::Ok, <S as serde::Serializer>::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_inner().serialize(serializer)
+    }
+}
+
+#[derive(Wrapper, Clone, PartialEq, Eq, Debug, From, Default, StrictEncode, StrictDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub struct OptionDetails(pub Option<String>);
+
+impl Display for OptionDetails {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.as_inner() {
+            None => Ok(()),
+            Some(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl OptionDetails {
+    pub fn with(s: impl ToString) -> Self {
+        Self(Some(s.to_string()))
+    }
+
+    pub fn new() -> Self {
+        Self(None)
     }
 }
 
 impl From<crate::Error> for Request {
     fn from(err: crate::Error) -> Self {
+        let code = match err {
+            crate::Error::BinaryNotFound(..) => FailureCode::BinaryNotFound,
+            _ => FailureCode::Unknown,
+        };
         Request::Failure(Failure {
-            code: FailureCode::Unknown,
+            code,
             info: err.to_string(),
         })
     }
@@ -12,10 +12,11 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use crate::rpc::request::{Failure, Progress, Request};
+use crate::rpc::request::{BusMessageCount, Failure, Progress, Request};
 use crate::rpc::ServiceBus;
 use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 use bitcoin::hashes::hex::{self, ToHex};
 use internet2::{
@@ -96,6 +97,12 @@ pub struct ServiceConfig {
 
     /// ZMQ socket for internal service control bus
     pub ctl_endpoint: ServiceAddr,
+
+    /// High-water mark of the outgoing queues of the bus sockets, ZMQ default if none
+    pub send_hwm: Option<i32>,
+
+    /// High-water mark of the incoming queues of the bus sockets, ZMQ default if none
+    pub receive_hwm: Option<i32>,
 }
 
 #[cfg(feature = "shell")]
@@ -104,6 +111,8 @@ impl From<Opts> for ServiceConfig {
         ServiceConfig {
             msg_endpoint: opts.msg_socket,
             ctl_endpoint: opts.ctl_socket,
+            send_hwm: opts.zmq_send_hwm,
+            receive_hwm: opts.zmq_receive_hwm,
         }
     }
 }
@@ -131,6 +140,10 @@ pub enum ServiceId {
     #[display("{0} ({1}) syncer")]
     Syncer(Blockchain, Network),
 
+    /// Syncer launched with servers overriding the configured ones, identified by its servers
+    #[display("{0} ({1}) syncer<{2:#x}>")]
+    CustomSyncer(Blockchain, Network, u64),
+
     #[display("walletd")]
     Wallet,
 
@@ -145,6 +158,10 @@ pub enum ServiceId {
 
     #[display("other<{0}>")]
     Other(ClientName),
+
+    /// Syncer instance named in the configuration, swaps can be pinned to it
+    #[display("{0} ({1}) syncer<{2}>")]
+    NamedSyncer(Blockchain, Network, String),
 }
 
 impl ServiceId {
@@ -156,6 +173,36 @@ impl ServiceId {
         use bitcoin::secp256k1::rand;
         ServiceId::Client(rand::random())
     }
+
+    pub fn syncer(
+        blockchain: Blockchain,
+        network: Network,
+        custom_id: Option<u64>,
+        instance: Option<String>,
+    ) -> ServiceId {
+        match (instance, custom_id) {
+            (Some(name), _) => ServiceId::NamedSyncer(blockchain, network, name),
+            (None, Some(id)) => ServiceId::CustomSyncer(blockchain, network, id),
+            (None, None) => ServiceId::Syncer(blockchain, network),
+        }
+    }
+
+    /// Returns the blockchain and network of a syncer
+    pub fn syncer_chain(&self) -> Option<(Blockchain, Network)> {
+        match self {
+            ServiceId::Syncer(blockchain, network)
+            | ServiceId::CustomSyncer(blockchain, network, _)
+            | ServiceId::NamedSyncer(blockchain, network, _) => Some((*blockchain, *network)),
+            _ => None,
+        }
+    }
+
+    pub fn is_syncer(&self) -> bool {
+        matches!(
+            self,
+            ServiceId::Syncer(..) | ServiceId::CustomSyncer(..) | ServiceId::NamedSyncer(..)
+        )
+    }
 }
 
 impl esb::ServiceAddress for ServiceId {}
@@ -198,11 +245,7 @@ where
         unreachable!()
     }
 
-    fn with(
-        config: ServiceConfig,
-        runtime: Runtime,
-        broker: bool,
-    ) -> Result<Self, esb::Error<ServiceId>> {
+    fn with(config: ServiceConfig, runtime: Runtime, broker: bool) -> Result<Self, Error> {
         let router = if !broker {
             Some(ServiceId::router())
         } else {
@@ -213,29 +256,42 @@ where
         } else {
             ZmqSocketType::RouterConnect
         };
-        let services = map! {
+        let mut services = map! {
             ServiceBus::Msg => esb::BusConfig::with_addr(
-                config.msg_endpoint,
+                config.msg_endpoint.clone(),
                 api_type,
                 router.clone()
             ),
             ServiceBus::Ctl => esb::BusConfig::with_addr(
-                config.ctl_endpoint,
+                config.ctl_endpoint.clone(),
                 api_type,
                 router
             )
         };
+        // the esb only sets up sockets with the ZMQ defaults, the tuned ones are handed over
+        if config.send_hwm.is_some() || config.receive_hwm.is_some() {
+            let identity = runtime.identity();
+            for (bus, endpoint) in [
+                (ServiceBus::Msg, &config.msg_endpoint),
+                (ServiceBus::Ctl, &config.ctl_endpoint),
+            ] {
+                let socket = bus_socket(&config, endpoint, broker, &identity)?;
+                if let Some(bus_config) = services.get_mut(&bus) {
+                    bus_config.carrier = zeromq::Carrier::Socket(socket);
+                }
+            }
+        }
 
         let esb = esb::Controller::with(services, runtime)?;
         Ok(Self { esb, broker })
     }
 
-    pub fn broker(config: ServiceConfig, runtime: Runtime) -> Result<Self, esb::Error<ServiceId>> {
+    pub fn broker(config: ServiceConfig, runtime: Runtime) -> Result<Self, Error> {
         Self::with(config, runtime, true)
     }
 
     #[allow(clippy::self_named_constructors)]
-    pub fn service(config: ServiceConfig, runtime: Runtime) -> Result<Self, esb::Error<ServiceId>> {
+    pub fn service(config: ServiceConfig, runtime: Runtime) -> Result<Self, Error> {
         Self::with(config, runtime, false)
     }
 
@@ -279,8 +335,131 @@ where
     }
 }
 
+/// Creates the router socket of a bus with the configured high-water marks, bound by the broker
+/// and connected under the service identity by the other services. The high-water marks only
+/// apply to the connections made after they are set.
+fn bus_socket(
+    config: &ServiceConfig,
+    endpoint: &ServiceAddr,
+    broker: bool,
+    identity: &ServiceId,
+) -> Result<zmq::Socket, Error> {
+    let socket = ZMQ_CONTEXT.socket(zmq::ROUTER)?;
+    if let Some(hwm) = config.send_hwm {
+        socket.set_sndhwm(hwm)?;
+    }
+    if let Some(hwm) = config.receive_hwm {
+        socket.set_rcvhwm(hwm)?;
+    }
+    let endpoint = match endpoint {
+        ServiceAddr::Inproc(name) => format!("inproc://{}", name),
+        ServiceAddr::Ipc(path) => format!("ipc://{}", path),
+        ServiceAddr::Tcp(addr) => format!("tcp://{}", addr),
+        endpoint => endpoint.to_string(),
+    };
+    if broker {
+        socket.bind(&endpoint)?;
+    } else {
+        let identity: Vec<u8> = identity.clone().into();
+        socket.set_identity(&identity)?;
+        socket.connect(&endpoint)?;
+    }
+    Ok(socket)
+}
+
 pub type Endpoints = esb::EndpointList<ServiceBus>;
 
+/// Log target of the esb wire trace, it can be filtered out with e.g.
+/// `RUST_LOG=info,farcaster_node::esb_trace=off`
+pub const ESB_TRACE_TARGET: &str = "farcaster_node::esb_trace";
+
+static ESB_TRACE: AtomicBool = AtomicBool::new(false);
+
+/// Enables the esb wire trace of this process, off by default due to its volume
+pub fn enable_esb_trace() {
+    ESB_TRACE.store(true, Ordering::Relaxed);
+}
+
+/// Logs a line for a request sent or received on a bus if the esb wire trace is enabled. Only the
+/// type of the request is logged, never its content, which may carry secrets such as keys or
+/// tenant tokens.
+pub fn trace_esb(bus: ServiceBus, source: &ServiceId, dest: &ServiceId, request: &Request) {
+    if ESB_TRACE.load(Ordering::Relaxed) {
+        let name = request_name(request);
+        info!(
+            target: ESB_TRACE_TARGET,
+            "{} | {} -> {} | {} ({})",
+            bus,
+            source,
+            dest,
+            name,
+            request.get_type()
+        );
+    }
+}
+
+/// Name of the variant of a request, without its content
+pub fn request_name(request: &Request) -> String {
+    format!("{:?}", request)
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+struct BusCounter {
+    sent: AtomicU64,
+    received: AtomicU64,
+}
+
+impl BusCounter {
+    const fn new() -> Self {
+        BusCounter {
+            sent: AtomicU64::new(0),
+            received: AtomicU64::new(0),
+        }
+    }
+}
+
+static MSG_BUS_COUNTER: BusCounter = BusCounter::new();
+static CTL_BUS_COUNTER: BusCounter = BusCounter::new();
+static BRIDGE_BUS_COUNTER: BusCounter = BusCounter::new();
+
+fn bus_counter(bus: ServiceBus) -> &'static BusCounter {
+    match bus {
+        ServiceBus::Msg => &MSG_BUS_COUNTER,
+        ServiceBus::Ctl => &CTL_BUS_COUNTER,
+        ServiceBus::Bridge => &BRIDGE_BUS_COUNTER,
+    }
+}
+
+/// Counts a request received on a bus by this process
+pub fn count_received(bus: ServiceBus) {
+    bus_counter(bus).received.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Number of requests this process sent and received on a bus since startup
+pub fn bus_message_count(bus: ServiceBus) -> BusMessageCount {
+    let counter = bus_counter(bus);
+    BusMessageCount {
+        sent: counter.sent.load(Ordering::Relaxed),
+        received: counter.received.load(Ordering::Relaxed),
+    }
+}
+
+/// Sends a request through a bus, traced if the esb wire trace is enabled and counted in the
+/// bus message counts of the process
+pub fn send_traced(
+    endpoints: &mut Endpoints,
+    bus: ServiceBus,
+    source: ServiceId,
+    dest: ServiceId,
+    request: Request,
+) -> Result<(), esb::Error<ServiceId>> {
+    trace_esb(bus, &source, &dest, &request);
+    bus_counter(bus).sent.fetch_add(1, Ordering::Relaxed);
+    endpoints.send_to(bus, source, dest, request)
+}
+
 pub trait TryToServiceId {
     fn try_to_service_id(&self) -> Option<ServiceId>;
 }
@@ -315,7 +494,8 @@ where
         msg: Option<impl ToString>,
     ) -> Result<(), Error> {
         if let Some(dest) = dest.try_to_service_id() {
-            senders.send_to(
+            send_traced(
+                senders,
                 ServiceBus::Ctl,
                 self.identity(),
                 dest,
@@ -332,7 +512,8 @@ where
         msg: impl ToString,
     ) -> Result<(), Error> {
         if let Some(dest) = dest.try_to_service_id() {
-            senders.send_to(
+            send_traced(
+                senders,
                 ServiceBus::Ctl,
                 self.identity(),
                 dest,
@@ -349,7 +530,8 @@ where
         msg: impl ToString,
     ) -> Result<(), Error> {
         if let Some(dest) = dest.try_to_service_id() {
-            senders.send_to(
+            send_traced(
+                senders,
                 ServiceBus::Ctl,
                 self.identity(),
                 dest,
@@ -367,7 +549,8 @@ where
     ) -> Error {
         if let Some(dest) = dest.try_to_service_id() {
             // Even if we fail, we still have to terminate :)
-            let _ = senders.send_to(
+            let _ = send_traced(
+                senders,
                 ServiceBus::Ctl,
                 self.identity(),
                 dest,
@@ -384,7 +567,7 @@ where
         request: Request,
     ) -> Result<(), Error> {
         if let Some(dest) = dest.try_to_service_id() {
-            senders.send_to(ServiceBus::Ctl, self.identity(), dest, request)?;
+            send_traced(senders, ServiceBus::Ctl, self.identity(), dest, request)?;
         }
         Ok(())
     }
@@ -397,9 +580,7 @@ where
     ) -> Result<(), Error> {
         let source = self.identity();
         trace!("sending {} to walletd from {}", request, source);
-        senders
-            .send_to(bus, source, ServiceId::Wallet, request)
-            .map_err(From::from)
+        send_traced(senders, bus, source, ServiceId::Wallet, request).map_err(From::from)
     }
 
     fn send_client_ctl(
@@ -410,9 +591,9 @@ where
     ) -> Result<(), Error> {
         let bus = ServiceBus::Ctl;
         if let ServiceId::GrpcdClient(_) = dest {
-            senders.send_to(bus, dest, ServiceId::Grpcd, request)?;
+            send_traced(senders, bus, dest, ServiceId::Grpcd, request)?;
         } else {
-            senders.send_to(bus, self.identity(), dest, request)?;
+            send_traced(senders, bus, self.identity(), dest, request)?;
         }
         Ok(())
     }
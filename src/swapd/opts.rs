@@ -35,6 +35,36 @@ pub struct Opts {
     #[clap(parse(try_from_str = FromStr::from_str))]
     pub trade_role: TradeRole,
 
+    /// Identifier of the custom Bitcoin syncer of the swap, if it overrides the configured
+    /// syncer servers
+    #[clap(long)]
+    pub arbitrating_syncer_id: Option<u64>,
+
+    /// Identifier of the custom Monero syncer of the swap, if it overrides the configured
+    /// syncer servers
+    #[clap(long)]
+    pub accordant_syncer_id: Option<u64>,
+
+    /// Name of the syncer instance serving the Bitcoin side of the swap, if the swap is pinned
+    /// to one
+    #[clap(long)]
+    pub arbitrating_syncer_instance: Option<String>,
+
+    /// Name of the syncer instance serving the Monero side of the swap, if the swap is pinned
+    /// to one
+    #[clap(long)]
+    pub accordant_syncer_instance: Option<String>,
+
+    /// Confirmation count of the funding of the swap at which a progress message is reported,
+    /// repeated for each threshold
+    #[clap(long)]
+    pub funding_progress_confirmations: Vec<u32>,
+
+    /// Only let the clients of the tenant owning the swap control it, farcasterd tells which
+    /// clients these are
+    #[clap(long)]
+    pub multi_tenant: bool,
+
     /// These params can be read also from the configuration file, not just
     /// Command-line args or environment variables
     #[clap(flatten)]
@@ -19,12 +19,13 @@ use crate::syncerd::bitcoin_syncer::p2wpkh_signed_tx_fee;
 use crate::syncerd::{FeeEstimation, FeeEstimations, SweepAddressAddendum};
 use crate::{
     rpc::request::Outcome,
-    rpc::request::{BitcoinFundingInfo, FundingInfo, MoneroFundingInfo},
+    rpc::request::{BitcoinFundingInfo, FundingCancelReason, FundingInfo, MoneroFundingInfo},
     syncerd::{
-        Abort, HeightChanged, SweepSuccess, TaskTarget, TransactionRetrieved, XmrAddressAddendum,
+        Abort, HeightChanged, Reorg, SweepSuccess, TaskId, TaskTarget, TransactionRetrieved,
+        XmrAddressAddendum,
     },
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::{
     io::Cursor,
     time::{Duration, SystemTime},
@@ -36,7 +37,7 @@ use super::{
     temporal_safety::TemporalSafety,
 };
 use crate::rpc::{
-    request::{self, Failure, FailureCode, Msg},
+    request::{self, Failure, FailureCode, MessageStats, Msg},
     Request, ServiceBus,
 };
 use crate::{CtlServer, Error, LogStyle, Service, ServiceConfig, ServiceId};
@@ -58,11 +59,18 @@ use microservices::esb::{self, Handler};
 use request::{CheckpointState, Commit, InitSwap, Params, Reveal, TakeCommit, Tx};
 use strict_encoding::{StrictDecode, StrictEncode};
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     config: ServiceConfig,
     swap_id: SwapId,
     public_offer: PublicOffer,
     local_trade_role: TradeRole,
+    arbitrating_syncer_id: Option<u64>,
+    accordant_syncer_id: Option<u64>,
+    arbitrating_syncer_instance: Option<String>,
+    accordant_syncer_instance: Option<String>,
+    funding_progress_confirmations: Vec<u32>,
+    multi_tenant: bool,
 ) -> Result<(), Error> {
     let Offer {
         cancel_timelock,
@@ -124,12 +132,25 @@ pub fn run(
         confirmation_bound: 50000,
         lock_tx_confs: None,
         cancel_tx_confs: None,
+        lock_tx_height: None,
+        cancel_tx_height: None,
         network,
-        bitcoin_syncer: ServiceId::Syncer(Blockchain::Bitcoin, network),
-        monero_syncer: ServiceId::Syncer(Blockchain::Monero, network),
+        bitcoin_syncer: ServiceId::syncer(
+            Blockchain::Bitcoin,
+            network,
+            arbitrating_syncer_id,
+            arbitrating_syncer_instance,
+        ),
+        monero_syncer: ServiceId::syncer(
+            Blockchain::Monero,
+            network,
+            accordant_syncer_id,
+            accordant_syncer_instance,
+        ),
         monero_amount,
         bitcoin_amount,
         awaiting_funding: false,
+        funding_reorged: false,
         xmr_addr_addendum: None,
         btc_fee_estimate_sat_per_kvb: None,
     };
@@ -148,6 +169,12 @@ pub fn run(
         pending_peer_request: none!(),
         txs: none!(),
         public_offer,
+        message_stats: none!(),
+        reported_txids: none!(),
+        funding_cancel_reason: None,
+        funding_progress_confirmations,
+        funding_progress_reported: None,
+        authorized_clients: if multi_tenant { Some(none!()) } else { None },
     };
     let broker = false;
     Service::run(config, runtime, broker)
@@ -169,6 +196,12 @@ pub struct Runtime {
     pending_peer_request: Vec<request::Msg>, // Peer requests that failed and are waiting for reconnection
     txs: HashMap<TxLabel, bitcoin::Transaction>,
     public_offer: PublicOffer,
+    message_stats: MessageStats,
+    reported_txids: HashSet<TxLabel>,
+    funding_cancel_reason: Option<FundingCancelReason>, // Why the funding is reported canceled once swept
+    funding_progress_confirmations: Vec<u32>, // Confirmation counts of our lock tx at which a progress message is reported, in increasing order
+    funding_progress_reported: Option<u32>, // The highest of these confirmation counts reported so far
+    authorized_clients: Option<HashSet<ServiceId>>, // The clients of the tenant owning the swap, set by farcasterd in multi-tenant mode, none if any client controls the swap
 }
 
 // FIXME Something more meaningful than ServiceId to index
@@ -452,11 +485,13 @@ impl Runtime {
             msg.bright_yellow_bold(),
             self.peer_service
         );
+        let request = Request::Protocol(msg.clone());
+        let len = request.serialize().len();
         if let Err(error) = endpoints.send_to(
             ServiceBus::Msg,
             self.identity(),
             self.peer_service.clone(), // ServiceId::Loopback if not initiailized
-            Request::Protocol(msg.clone()),
+            request,
         ) {
             error!(
                 "could not send message {} to {} due to {}",
@@ -470,6 +505,8 @@ impl Runtime {
                 Request::PeerdUnreachable(self.peer_service.clone()),
             )?;
             self.pending_peer_request.push(msg);
+        } else {
+            self.message_stats.record_sent(len);
         }
         Ok(())
     }
@@ -512,6 +549,7 @@ impl Runtime {
             tx_label.bright_white_bold(),
             tx.txid().bright_yellow_italic()
         );
+        self.report_txid(endpoints, tx_label, tx.txid())?;
         let task = self.syncer_state.broadcast(tx);
         Ok(endpoints.send_to(
             ServiceBus::Ctl,
@@ -543,6 +581,8 @@ impl Runtime {
                         msg.swap_id(),
                     )));
                 } else {
+                    self.message_stats
+                        .record_received(request.serialize().len());
                     msg
                 }
             }
@@ -809,7 +849,24 @@ impl Runtime {
             }
 
             // bob and alice
-            Msg::Abort(_) => return Err(Error::Farcaster("Abort not yet supported".to_string())),
+            // the counterparty refused or gave up the swap, it is aborted as if by the user while
+            // the funds are not locked, the enquirer learns why
+            Msg::Abort(abort) => {
+                let mut failure = Failure::from_abort_body(
+                    abort.error_body.as_deref().unwrap_or("no reason given"),
+                    FailureCode::CounterpartyAborted,
+                );
+                // the failures without a specific code are reported as a plain abort
+                if failure.code == FailureCode::Unknown {
+                    failure.code = FailureCode::CounterpartyAborted;
+                }
+                failure.info = format!("The counterparty aborted the swap: {}", failure.info);
+                warn!("{} | {}", self.swap_id.bright_blue_italic(), failure.info);
+                if let Some(enquirer) = self.enquirer.clone() {
+                    let _ = self.send_ctl(endpoints, enquirer, Request::Failure(failure));
+                }
+                self.handle_rpc_ctl(endpoints, ServiceId::Farcasterd, Request::AbortSwap)?;
+            }
             Msg::Ping(_) | Msg::Pong(_) | Msg::PingPeer => {
                 unreachable!("ping/pong must remain in peerd, and unreachable in swapd")
             }
@@ -836,7 +893,11 @@ impl Runtime {
                     "connected"
                 );
             }
-            (_, ServiceId::Syncer(..)) if self.syncer_state.any_syncer(&source) => {
+            (
+                _,
+                ServiceId::Syncer(..) | ServiceId::CustomSyncer(..) | ServiceId::NamedSyncer(..),
+            )
+                if self.syncer_state.any_syncer(&source) => {
             }
             (
                 _,
@@ -844,7 +905,29 @@ impl Runtime {
                 | ServiceId::Wallet
                 | ServiceId::Database
             ) => {}
+            // in multi-tenant mode the clients of other tenants are answered as if the swap
+            // did not exist
+            (_, ServiceId::Client(_)) if !self.is_authorized_client(&source) => {
+                warn!(
+                    "{} | Rejecting request {} from {}, it is not a client of the tenant owning the swap",
+                    self.swap_id.bright_blue_italic(),
+                    request,
+                    source
+                );
+                self.send_ctl(
+                    endpoints,
+                    source.clone(),
+                    Request::Failure(Failure {
+                        code: FailureCode::Unknown,
+                        info: format!("Unknown swap {}", self.swap_id),
+                    }),
+                )?;
+                return Ok(());
+            }
             (Request::AbortSwap, ServiceId::Client(_)) => {}
+            (Request::SetConfirmationTarget(_), ServiceId::Client(_)) => {}
+            (Request::ReissueSwapTasks(_), ServiceId::Client(_)) => {}
+            (Request::SwapTimelocks(_), ServiceId::Client(_)) => {}
             (Request::GetInfo, ServiceId::Client(_)) => {}
             _ => return Err(Error::Farcaster(
                 "Permission Error: only Farcasterd, Wallet, Client and Syncer can can control swapd"
@@ -853,6 +936,9 @@ impl Runtime {
         };
 
         match request {
+            Request::SwapClients(clients) if source == ServiceId::Farcasterd => {
+                self.authorized_clients = Some(clients.into_iter().collect());
+            }
             Request::Terminate if source == ServiceId::Farcasterd => {
                 info!(
                     "{} | {}",
@@ -1019,7 +1105,21 @@ impl Runtime {
             // handle monero events here
             // }
             Request::SyncerEvent(ref event) if source == self.syncer_state.monero_syncer => {
+                if let Event::TransactionConfirmations(TransactionConfirmations {
+                    id,
+                    confirmations: Some(confirmations),
+                    ..
+                }) = &event
+                {
+                    if *confirmations > 0 {
+                        self.handle_funding_reconfirmed(endpoints, id, Blockchain::Monero)?;
+                    }
+                    self.report_funding_confirmations(endpoints, id, *confirmations)?;
+                }
                 match &event {
+                    Event::Reorg(Reorg { id, .. }) => {
+                        self.handle_reorg(endpoints, id, Blockchain::Monero)?;
+                    }
                     Event::HeightChanged(HeightChanged { height, .. }) => {
                         self.syncer_state
                             .handle_height_change(*height, Blockchain::Monero);
@@ -1318,6 +1418,7 @@ impl Runtime {
                         if let Some(success) = success {
                             let swap_success_req = Request::SwapOutcome(success);
                             self.send_ctl(endpoints, ServiceId::Wallet, swap_success_req.clone())?;
+                            self.report_message_stats(endpoints)?;
                             self.send_ctl(endpoints, ServiceId::Farcasterd, swap_success_req)?;
                             // remove txs to invalidate outdated states
                             self.txs.remove(&TxLabel::Cancel);
@@ -1332,7 +1433,26 @@ impl Runtime {
                 }
             }
             Request::SyncerEvent(ref event) if source == self.syncer_state.bitcoin_syncer => {
+                if let Event::TransactionConfirmations(TransactionConfirmations {
+                    id,
+                    confirmations: Some(confirmations),
+                    ..
+                }) = &event
+                {
+                    if let Some(tx_label) = self.syncer_state.tasks.watched_txs.get(id).copied() {
+                        if let Some(txid) = self.syncer_state.tasks.txids.get(&tx_label).copied() {
+                            self.report_txid(endpoints, tx_label, txid)?;
+                        }
+                    }
+                    if *confirmations > 0 {
+                        self.handle_funding_reconfirmed(endpoints, id, Blockchain::Bitcoin)?;
+                    }
+                    self.report_funding_confirmations(endpoints, id, *confirmations)?;
+                }
                 match &event {
+                    Event::Reorg(Reorg { id, .. }) => {
+                        self.handle_reorg(endpoints, id, Blockchain::Bitcoin)?;
+                    }
                     Event::HeightChanged(HeightChanged { height, .. }) => {
                         self.syncer_state
                             .handle_height_change(*height, Blockchain::Bitcoin);
@@ -1352,6 +1472,7 @@ impl Runtime {
                                     && self.state.b_required_funding_amount().is_some() =>
                             {
                                 log_tx_seen(self.swap_id, txlabel, &tx.txid());
+                                self.report_txid(endpoints, TxLabel::Funding, tx.txid())?;
                                 self.syncer_state.awaiting_funding = false;
                                 endpoints.send_to(
                                     ServiceBus::Ctl,
@@ -1372,6 +1493,13 @@ impl Runtime {
                                         ServiceId::Farcasterd,
                                         msg,
                                     )?;
+                                    // funding more than required is a user mistake too
+                                    self.funding_cancel_reason =
+                                        Some(if amount < required_funding_amount {
+                                            FundingCancelReason::InsufficientBalance
+                                        } else {
+                                            FundingCancelReason::UserAborted
+                                        });
                                     // FIXME: syncer shall not have permission to AbortSwap, replace source by identity?
                                     self.handle_rpc_ctl(endpoints, source, Request::AbortSwap)?;
                                     return Ok(());
@@ -1401,6 +1529,7 @@ impl Runtime {
                     {
                         let (txlabel, _) =
                             self.syncer_state.tasks.retrieving_txs.remove(id).unwrap();
+                        self.syncer_state.tasks.tasks.remove(id);
                         match txlabel {
                             TxLabel::Buy if self.state.b_buy_sig() => {
                                 log_tx_seen(self.swap_id, &txlabel, &tx.txid());
@@ -1470,9 +1599,13 @@ impl Runtime {
                         match &txlabel {
                             TxLabel::Lock => {
                                 self.syncer_state.lock_tx_confs = Some(request.clone());
+                                self.syncer_state.lock_tx_height =
+                                    self.syncer_state.mined_height(*confirmations);
                             }
                             TxLabel::Cancel => {
                                 self.syncer_state.cancel_tx_confs = Some(request.clone());
+                                self.syncer_state.cancel_tx_height =
+                                    self.syncer_state.mined_height(*confirmations);
                                 self.state.sup_cancel_seen();
                             }
 
@@ -1615,7 +1748,10 @@ impl Runtime {
                                     ServiceBus::Ctl,
                                     self.identity(),
                                     ServiceId::Farcasterd,
-                                    Request::FundingCanceled(Blockchain::Monero),
+                                    Request::FundingCanceled(
+                                        Blockchain::Monero,
+                                        FundingCancelReason::Timeout,
+                                    ),
                                 )?
                             }
 
@@ -1668,7 +1804,10 @@ impl Runtime {
                                         ServiceBus::Ctl,
                                         self.identity(),
                                         ServiceId::Farcasterd,
-                                        Request::FundingCanceled(Blockchain::Monero),
+                                        Request::FundingCanceled(
+                                            Blockchain::Monero,
+                                            FundingCancelReason::Timeout,
+                                        ),
                                     )?;
                                     self.syncer_state.awaiting_funding = false;
                                 }
@@ -1698,6 +1837,7 @@ impl Runtime {
                                     endpoints,
                                     swap_success_req.clone(),
                                 )?;
+                                self.report_message_stats(endpoints)?;
                                 self.send_ctl(endpoints, ServiceId::Farcasterd, swap_success_req)?;
                                 self.txs.remove(&TxLabel::Buy);
                                 self.txs.remove(&TxLabel::Cancel);
@@ -1737,6 +1877,7 @@ impl Runtime {
                                     endpoints,
                                     swap_success_req.clone(),
                                 )?;
+                                self.report_message_stats(endpoints)?;
                                 self.send_ctl(endpoints, ServiceId::Farcasterd, swap_success_req)?;
                                 self.txs.remove(&TxLabel::Cancel);
                                 self.txs.remove(&TxLabel::Punish);
@@ -1801,6 +1942,7 @@ impl Runtime {
                                     ServiceId::Wallet,
                                     swap_success_req.clone(),
                                 )?;
+                                self.report_message_stats(endpoints)?;
                                 self.send_ctl(endpoints, ServiceId::Farcasterd, swap_success_req)?;
                                 // remove txs to invalidate outdated states
                                 self.txs.remove(&TxLabel::Cancel);
@@ -1844,6 +1986,7 @@ impl Runtime {
                                     ServiceId::Wallet,
                                     swap_success_req.clone(),
                                 )?;
+                                self.report_message_stats(endpoints)?;
                                 self.send_ctl(endpoints, ServiceId::Farcasterd, swap_success_req)?;
                                 // remove txs to invalidate outdated states
                                 self.txs.remove(&TxLabel::Cancel);
@@ -1891,13 +2034,20 @@ impl Runtime {
                             ServiceBus::Ctl,
                             self.identity(),
                             ServiceId::Farcasterd,
-                            Request::FundingCanceled(Blockchain::Bitcoin),
+                            Request::FundingCanceled(
+                                Blockchain::Bitcoin,
+                                self.funding_cancel_reason
+                                    .unwrap_or(FundingCancelReason::UserAborted),
+                            ),
                         )?;
                         self.abort_swap(endpoints)?;
                     }
                     Event::SweepSuccess(event) => {
                         debug!("{}", event)
                     }
+                    Event::SweepStatus(event) => {
+                        debug!("{}", event)
+                    }
                     Event::TransactionRetrieved(event) => {
                         debug!("{}", event)
                     }
@@ -2270,6 +2420,10 @@ impl Runtime {
                     ServiceId::Wallet,
                     Request::GetSweepBitcoinAddress(self.state.b_address().cloned().unwrap()),
                 )?;
+                self.funding_cancel_reason.get_or_insert(match source {
+                    ServiceId::Client(_) => FundingCancelReason::UserAborted,
+                    _ => FundingCancelReason::CounterpartyFailed,
+                });
                 // cancel the swap to invalidate its state
                 self.state_update(endpoints, State::Bob(BobState::FinishB(Outcome::Abort)))?;
                 if let ServiceId::Client(_) = source {
@@ -2297,6 +2451,113 @@ impl Runtime {
                     )?;
                 }
             }
+            // Sends the syncer tasks of the swap again, e.g. after a syncer restart lost them,
+            // they keep their id so their events are handled as before
+            Request::ReissueSwapTasks(_) => {
+                let tasks = self.syncer_state.reissued_tasks();
+                let count = tasks.len();
+                for (syncer, task) in tasks {
+                    self.send_ctl(endpoints, syncer, Request::SyncerTask(task))?;
+                }
+                info!(
+                    "{} | Reissued {} syncer tasks",
+                    self.swap_id.bright_blue_italic(),
+                    count
+                );
+                self.send_ctl(
+                    endpoints,
+                    source,
+                    Request::String(format!("Reissued {} syncer tasks", count)),
+                )?;
+            }
+
+            // The cancel transaction is valid once the lock has cancel timelock confirmations, and
+            // the punish transaction once the cancel has punish timelock confirmations
+            Request::SwapTimelocks(_) => {
+                let cancel_timelock = self.temporal_safety.cancel_timelock;
+                let punish_timelock = self.temporal_safety.punish_timelock;
+                let lock_height = self.syncer_state.lock_tx_height;
+                let cancel_valid_height = lock_height.map(|height| height + cancel_timelock as u64);
+                let punish_valid_height = self
+                    .syncer_state
+                    .cancel_tx_height
+                    .or(cancel_valid_height)
+                    .map(|height| height + punish_timelock as u64);
+                let timelocks = request::SwapTimelocks {
+                    swap_id: self.swap_id,
+                    cancel_timelock,
+                    punish_timelock,
+                    current_height: Some(self.syncer_state.bitcoin_height)
+                        .filter(|height| *height > 0),
+                    lock_height,
+                    cancel_valid_height,
+                    punish_valid_height,
+                };
+                self.send_ctl(endpoints, source, Request::SwapTimelocksReport(timelocks))?;
+            }
+
+            Request::SetConfirmationTarget(request::ConfirmationTarget {
+                blockchain,
+                confs,
+                ..
+            }) => {
+                // the threshold only matters until the lock on that blockchain is final, changing
+                // it afterwards would not be applied consistently
+                let lock_final = self.state.finish()
+                    || match blockchain {
+                        Blockchain::Bitcoin => self.syncer_state.lock_tx_confs.is_some(),
+                        Blockchain::Monero => self.state.a_xmr_locked() || self.state.b_buy_sig(),
+                    };
+                let mut temporal_safety = self.temporal_safety.clone();
+                match blockchain {
+                    Blockchain::Bitcoin => temporal_safety.btc_finality_thr = confs,
+                    Blockchain::Monero => temporal_safety.xmr_finality_thr = confs,
+                }
+                let res = if lock_final {
+                    Err(format!(
+                        "{} lock is already final, cannot change its confirmation target anymore",
+                        blockchain
+                    ))
+                } else if confs == 0 {
+                    Err(s!(
+                        "The confirmation target must be at least one confirmation"
+                    ))
+                } else {
+                    temporal_safety
+                        .valid_params()
+                        .map_err(|err| err.to_string())
+                };
+                match res {
+                    Ok(()) => {
+                        info!(
+                            "{} | Confirmation target on {} set to {}",
+                            self.swap_id.bright_blue_italic(),
+                            blockchain,
+                            confs
+                        );
+                        self.temporal_safety = temporal_safety;
+                        self.send_ctl(
+                            endpoints,
+                            source,
+                            Request::String(format!(
+                                "Confirmation target on {} set to {}",
+                                blockchain, confs
+                            )),
+                        )?;
+                    }
+                    Err(info) => {
+                        warn!("{} | {}", self.swap_id, info);
+                        self.send_ctl(
+                            endpoints,
+                            source,
+                            Request::Failure(Failure {
+                                code: FailureCode::Unknown,
+                                info,
+                            }),
+                        )?;
+                    }
+                }
+            }
             Request::GetInfo => {
                 let swap_id = if self.swap_id() == zero!() {
                     None
@@ -2316,10 +2577,27 @@ impl Runtime {
                         .unwrap_or_else(|_| Duration::from_secs(0))
                         .as_secs(),
                     public_offer: self.public_offer.clone(),
+                    message_stats: Some(self.message_stats),
                 };
                 self.send_ctl(endpoints, source, Request::SwapInfo(info))?;
             }
 
+            Request::RebindSwapConnection(request::RebindSwapConnection { new_peer, .. })
+                if source == ServiceId::Farcasterd =>
+            {
+                info!(
+                    "{} | Rebinding the connection from {} to {}",
+                    self.swap_id.bright_blue_italic(),
+                    self.peer_service,
+                    new_peer
+                );
+                self.peer_service = ServiceId::Peer(new_peer);
+                for msg in self.pending_peer_request.clone().iter() {
+                    self.send_peer(endpoints, msg.clone())?;
+                }
+                self.pending_peer_request.clear();
+            }
+
             Request::PeerdReconnected(service_id) => {
                 // set the reconnected service id, if it is not set yet. This
                 // can happen if this is a maker launched swap after restoration
@@ -2436,6 +2714,108 @@ impl Runtime {
 }
 
 impl Runtime {
+    /// Whether the transaction watched under this label is the one locking our own funds
+    fn is_own_lock_tx(&self, txlabel: &TxLabel) -> bool {
+        matches!(
+            (self.state.swap_role(), txlabel),
+            (SwapRole::Bob, TxLabel::Lock) | (SwapRole::Alice, TxLabel::AccLock)
+        )
+    }
+
+    fn handle_reorg(
+        &mut self,
+        endpoints: &mut Endpoints,
+        id: &TaskId,
+        blockchain: Blockchain,
+    ) -> Result<(), Error> {
+        let txlabel = match self.syncer_state.tasks.watched_txs.get(id) {
+            Some(txlabel) => *txlabel,
+            None => return Ok(()),
+        };
+        // the transaction has to reach finality again
+        self.syncer_state.tasks.final_txs.remove(&txlabel);
+        if self.is_own_lock_tx(&txlabel) {
+            self.funding_progress_reported = None;
+        }
+        let msg = format!(
+            "Tx {} was reorged out of the {} chain, awaiting confirmations again",
+            txlabel, blockchain
+        );
+        warn!("{} | {}", self.swap_id.bright_blue_italic(), msg);
+        self.report_progress_message_to(endpoints, ServiceId::Farcasterd, msg)?;
+        if self.is_own_lock_tx(&txlabel) && !self.syncer_state.funding_reorged {
+            self.syncer_state.funding_reorged = true;
+            endpoints.send_to(
+                ServiceBus::Ctl,
+                self.identity(),
+                ServiceId::Farcasterd,
+                Request::FundingReorged(blockchain),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn handle_funding_reconfirmed(
+        &mut self,
+        endpoints: &mut Endpoints,
+        id: &TaskId,
+        blockchain: Blockchain,
+    ) -> Result<(), Error> {
+        if !self.syncer_state.funding_reorged {
+            return Ok(());
+        }
+        match self.syncer_state.tasks.watched_txs.get(id) {
+            Some(txlabel) if self.is_own_lock_tx(txlabel) => {
+                info!(
+                    "{} | Tx {} confirmed again after reorg",
+                    self.swap_id.bright_blue_italic(),
+                    txlabel.bright_white_bold()
+                );
+                self.syncer_state.funding_reorged = false;
+                endpoints.send_to(
+                    ServiceBus::Ctl,
+                    self.identity(),
+                    ServiceId::Farcasterd,
+                    Request::FundingCompleted(blockchain),
+                )?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Reports a progress message once our lock tx reaches one of the configured confirmation
+    /// counts, instead of one per confirmation. Only the highest count reached is reported if
+    /// several are reached at once.
+    fn report_funding_confirmations(
+        &mut self,
+        endpoints: &mut Endpoints,
+        id: &TaskId,
+        confirmations: u32,
+    ) -> Result<(), Error> {
+        let txlabel = match self.syncer_state.tasks.watched_txs.get(id) {
+            Some(txlabel) if self.is_own_lock_tx(txlabel) => *txlabel,
+            _ => return Ok(()),
+        };
+        let reached = self
+            .funding_progress_confirmations
+            .iter()
+            .rev()
+            .find(|threshold| **threshold <= confirmations)
+            .copied();
+        match reached {
+            Some(threshold) if Some(threshold) > self.funding_progress_reported => {
+                self.funding_progress_reported = Some(threshold);
+                let msg = format!(
+                    "Tx {} funding the swap reached {} confirmations",
+                    txlabel, confirmations
+                );
+                self.report_progress_message_to(endpoints, ServiceId::Farcasterd, msg)
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn ask_bob_to_fund(
         &mut self,
         sat_per_kvb: u64,
@@ -2540,10 +2920,50 @@ impl Runtime {
     fn abort_swap(&mut self, endpoints: &mut Endpoints) -> Result<(), Error> {
         let swap_success_req = Request::SwapOutcome(Outcome::Abort);
         self.send_ctl(endpoints, ServiceId::Wallet, swap_success_req.clone())?;
+        self.report_message_stats(endpoints)?;
         self.send_ctl(endpoints, ServiceId::Farcasterd, swap_success_req)?;
         info!("{} | Aborted swap.", self.swap_id);
         Ok(())
     }
+
+    /// Report a transaction of the swap to farcasterd the first time it is broadcast or observed
+    fn report_txid(
+        &mut self,
+        endpoints: &mut Endpoints,
+        tx_label: TxLabel,
+        txid: Txid,
+    ) -> Result<(), Error> {
+        if self.reported_txids.insert(tx_label) {
+            self.send_ctl(
+                endpoints,
+                ServiceId::Farcasterd,
+                Request::SwapTxid(request::SwapTxid {
+                    tx_label,
+                    txid,
+                    explorer_url: None,
+                }),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns true if the client may control the swap, any client may unless farcasterd
+    /// restricted the swap to the clients of its tenant
+    fn is_authorized_client(&self, client: &ServiceId) -> bool {
+        self.authorized_clients
+            .as_ref()
+            .map_or(true, |clients| clients.contains(client))
+    }
+
+    /// Report the protocol messages exchanged over the swap to farcasterd before the outcome, so
+    /// they are accounted for in the node stats
+    fn report_message_stats(&mut self, endpoints: &mut Endpoints) -> Result<(), Error> {
+        self.send_ctl(
+            endpoints,
+            ServiceId::Farcasterd,
+            Request::SwapMessageStats(self.message_stats),
+        )
+    }
 }
 
 pub fn get_swap_id(source: &ServiceId) -> Result<SwapId, Error> {
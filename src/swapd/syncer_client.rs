@@ -29,7 +29,8 @@ pub struct SyncerTasks {
     pub sweeping_addr: Option<TaskId>,
     // external address: needed to subscribe for buy (bob) or refund (alice) address_txs
     pub txids: HashMap<TxLabel, Txid>,
-    pub tasks: HashMap<TaskId, Task>,
+    // the tasks sent to the syncers, along with the blockchain of their syncer
+    pub tasks: HashMap<TaskId, (Blockchain, Task)>,
 }
 
 impl SyncerTasks {
@@ -47,6 +48,8 @@ pub struct SyncerState {
     pub confirmation_bound: u32,
     pub lock_tx_confs: Option<Request>,
     pub cancel_tx_confs: Option<Request>,
+    pub lock_tx_height: Option<u64>,
+    pub cancel_tx_height: Option<u64>,
     pub network: farcaster_core::blockchain::Network,
     pub bitcoin_syncer: ServiceId,
     pub monero_syncer: ServiceId,
@@ -54,9 +57,18 @@ pub struct SyncerState {
     pub bitcoin_amount: bitcoin::Amount,
     pub xmr_addr_addendum: Option<XmrAddressAddendum>,
     pub awaiting_funding: bool,
+    pub funding_reorged: bool,
     pub btc_fee_estimate_sat_per_kvb: Option<u64>,
 }
 impl SyncerState {
+    /// Estimates the Bitcoin height a transaction was mined at from its confirmations and the
+    /// current height, none if the current height is not known yet
+    pub fn mined_height(&self, confirmations: u32) -> Option<u64> {
+        if self.bitcoin_height == 0 || confirmations == 0 {
+            return None;
+        }
+        Some((self.bitcoin_height + 1).saturating_sub(confirmations as u64))
+    }
     pub fn task_lifetime(&self, blockchain: Blockchain) -> u64 {
         let height = self.height(blockchain);
         if height > 0 {
@@ -102,6 +114,7 @@ impl SyncerState {
         }
     }
     pub fn abort_task(&mut self, id: TaskId) -> Task {
+        self.tasks.tasks.remove(&id);
         Task::Abort(Abort {
             task_target: TaskTarget::TaskId(id),
             respond: Boolean::False,
@@ -114,7 +127,9 @@ impl SyncerState {
             id,
             lifetime: self.task_lifetime(Blockchain::Bitcoin),
         });
-        self.tasks.tasks.insert(id, task.clone());
+        self.tasks
+            .tasks
+            .insert(id, (Blockchain::Bitcoin, task.clone()));
         task
     }
 
@@ -134,7 +149,9 @@ impl SyncerState {
             hash: txid.to_vec(),
             confirmation_bound: self.confirmation_bound,
         });
-        self.tasks.tasks.insert(id, task.clone());
+        self.tasks
+            .tasks
+            .insert(id, (Blockchain::Bitcoin, task.clone()));
         task
     }
     pub fn is_watched_tx(&self, tx_label: &TxLabel) -> bool {
@@ -156,7 +173,9 @@ impl SyncerState {
             hash,
             confirmation_bound: self.confirmation_bound,
         });
-        self.tasks.tasks.insert(id, task.clone());
+        self.tasks
+            .tasks
+            .insert(id, (Blockchain::Monero, task.clone()));
         task
     }
     pub fn retrieve_tx_btc(&mut self, txid: Txid, tx_label: TxLabel) -> Task {
@@ -168,7 +187,9 @@ impl SyncerState {
         self.tasks
             .retrieving_txs
             .insert(id, (tx_label, task.clone()));
-        self.tasks.tasks.insert(id, task.clone());
+        self.tasks
+            .tasks
+            .insert(id, (Blockchain::Bitcoin, task.clone()));
         task
     }
     pub fn watch_addr_btc(&mut self, address: bitcoin::Address, tx_label: TxLabel) -> Task {
@@ -191,7 +212,9 @@ impl SyncerState {
             addendum: AddressAddendum::Bitcoin(addendum),
             include_tx: Boolean::True,
         });
-        self.tasks.tasks.insert(id, task.clone());
+        self.tasks
+            .tasks
+            .insert(id, (Blockchain::Bitcoin, task.clone()));
         task
     }
 
@@ -251,7 +274,9 @@ impl SyncerState {
             include_tx: Boolean::False,
         };
         let task = Task::WatchAddress(watch_addr);
-        self.tasks.tasks.insert(id, task.clone());
+        self.tasks
+            .tasks
+            .insert(id, (Blockchain::Monero, task.clone()));
         task
     }
 
@@ -262,7 +287,7 @@ impl SyncerState {
             id,
             lifetime: self.task_lifetime(blockchain),
         });
-        self.tasks.tasks.insert(id, task.clone());
+        self.tasks.tasks.insert(id, (blockchain, task.clone()));
         task
     }
 
@@ -278,7 +303,9 @@ impl SyncerState {
             from_height: None,
         };
         let task = Task::SweepAddress(sweep_task);
-        self.tasks.tasks.insert(id, task.clone());
+        self.tasks
+            .tasks
+            .insert(id, (Blockchain::Bitcoin, task.clone()));
         task
     }
 
@@ -294,7 +321,9 @@ impl SyncerState {
             from_height: None,
         };
         let task = Task::SweepAddress(sweep_task);
-        self.tasks.tasks.insert(id, task.clone());
+        self.tasks
+            .tasks
+            .insert(id, (Blockchain::Monero, task.clone()));
         task
     }
 
@@ -304,7 +333,9 @@ impl SyncerState {
             id,
             tx: bitcoin::consensus::serialize(&tx),
         });
-        self.tasks.tasks.insert(id, task.clone());
+        self.tasks
+            .tasks
+            .insert(id, (Blockchain::Bitcoin, task.clone()));
         self.tasks.broadcasting_txs.insert(id);
         task
     }
@@ -317,7 +348,7 @@ impl SyncerState {
             .broadcasting_txs
             .iter()
             .filter_map(|id| {
-                if let Task::BroadcastTransaction(broadcast_tx) = self.tasks.tasks.get(id)? {
+                if let (_, Task::BroadcastTransaction(broadcast_tx)) = self.tasks.tasks.get(id)? {
                     Some(
                         bitcoin::Transaction::consensus_decode(std::io::Cursor::new(
                             broadcast_tx.tx.clone(),
@@ -331,6 +362,31 @@ impl SyncerState {
             .collect()
     }
 
+    /// The tasks to send again to the syncers, e.g. after a syncer restart lost them, along with
+    /// their syncer. Only the tasks still pending are reissued, i.e. not the aborted watches nor
+    /// the retrieved transactions. Sweeps are not reissued, sweeping twice would fail once the
+    /// first sweep completed.
+    pub fn reissued_tasks(&self) -> Vec<(ServiceId, Task)> {
+        let mut tasks: Vec<(&TaskId, &(Blockchain, Task))> = self
+            .tasks
+            .tasks
+            .iter()
+            .filter(|(id, (_, task))| match task {
+                Task::WatchHeight(_) | Task::WatchEstimateFee(_) => true,
+                Task::WatchTransaction(_) => self.tasks.watched_txs.contains_key(id),
+                Task::WatchAddress(_) => self.tasks.watched_addrs.contains_key(id),
+                Task::GetTx(_) => self.tasks.retrieving_txs.contains_key(id),
+                Task::BroadcastTransaction(_) => self.tasks.broadcasting_txs.contains(id),
+                _ => false,
+            })
+            .collect();
+        tasks.sort_by_key(|(id, _)| id.0);
+        tasks
+            .into_iter()
+            .map(|(_, (blockchain, task))| (self.syncer(*blockchain).clone(), task.clone()))
+            .collect()
+    }
+
     pub fn acc_lock_watched(&self) -> bool {
         self.tasks
             .watched_addrs
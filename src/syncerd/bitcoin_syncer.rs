@@ -2,6 +2,7 @@ use crate::error::SyncerError;
 use crate::rpc::request::SyncerdBridgeEvent;
 use crate::rpc::Request;
 use crate::syncerd::opts::Opts;
+use crate::syncerd::runtime::idle_if_paused;
 use crate::syncerd::runtime::SyncerdTask;
 use crate::syncerd::runtime::Synclet;
 use crate::syncerd::syncer_state::AddressTx;
@@ -32,6 +33,7 @@ use internet2::Encrypt;
 use internet2::PlainTranscoder;
 use internet2::TypedEnum;
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
 use std::sync::mpsc::{Receiver, TryRecvError};
 use std::sync::Arc;
 use std::time::Duration;
@@ -48,6 +50,7 @@ pub struct ElectrumRpc {
     client: Client,
     height: u64,
     block_hash: BlockHash,
+    block_time: u32,
     addresses: HashMap<BtcAddressAddendum, Option<Hex32Bytes>>,
     ping_count: u8,
 }
@@ -56,6 +59,7 @@ pub struct ElectrumRpc {
 pub struct Block {
     height: u64,
     block_hash: BlockHash,
+    time: u32,
 }
 
 #[derive(Debug)]
@@ -93,6 +97,7 @@ impl ElectrumRpc {
             addresses: none!(),
             height: header.height as u64,
             block_hash: header.header.block_hash(),
+            block_time: header.header.time,
             ping_count: 0,
         })
     }
@@ -139,10 +144,12 @@ impl ElectrumRpc {
         {
             self.height = height as u64;
             self.block_hash = header.block_hash();
+            self.block_time = header.time;
             trace!("new height received: {:?}", self.height);
             blocks.push(Block {
                 height: self.height,
                 block_hash: self.block_hash,
+                time: self.block_time,
             });
         }
         Ok(blocks)
@@ -519,12 +526,17 @@ async fn run_syncerd_bridge_event_sender(
 async fn run_syncerd_task_receiver(
     receive_task_channel: Receiver<SyncerdTask>,
     state: Arc<Mutex<SyncerState>>,
+    paused: Arc<AtomicBool>,
     transaction_broadcast_tx: TokioSender<(BroadcastTransaction, ServiceId)>,
     transaction_get_tx: TokioSender<(GetTx, ServiceId)>,
     terminate_tx: TokioSender<()>,
 ) {
     tokio::spawn(async move {
         loop {
+            // tasks are left in the channel while paused
+            if idle_if_paused(&paused).await {
+                continue;
+            }
             // this is a hack around the Receiver not being Sync
             let syncerd_task = receive_task_channel.try_recv();
             match syncerd_task {
@@ -600,6 +612,10 @@ async fn run_syncerd_task_receiver(
                             state_guard.watch_transaction(task, syncerd_task.source);
                             drop(state_guard);
                         }
+                        Task::GetSweepStatus(task) => {
+                            let mut state_guard = state.lock().await;
+                            state_guard.sweep_status(task, syncerd_task.source).await;
+                        }
                         Task::Terminate => {
                             debug!("terminating async syncer runtime");
                             terminate_tx
@@ -626,6 +642,7 @@ fn address_polling(
     state: Arc<Mutex<SyncerState>>,
     electrum_server: String,
     proxy_address: Option<String>,
+    paused: Arc<AtomicBool>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn(async move {
         loop {
@@ -643,6 +660,9 @@ fn address_polling(
             };
 
             loop {
+                if idle_if_paused(&paused).await {
+                    continue;
+                }
                 if let Err(err) = rpc.ping() {
                     error!("error ping electrum client in address polling: {:?}", err);
                     // break this loop and retry, since the electrum rpc client is probably
@@ -709,6 +729,7 @@ fn height_polling(
     state: Arc<Mutex<SyncerState>>,
     electrum_server: String,
     proxy_address: Option<String>,
+    paused: Arc<AtomicBool>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn(async move {
         // outer loop ensures the polling restarts if there is an error
@@ -728,11 +749,14 @@ fn height_polling(
 
             let mut state_guard = state.lock().await;
             state_guard
-                .change_height(rpc.height, rpc.block_hash.to_vec())
+                .change_height(rpc.height, rpc.block_hash.to_vec(), rpc.block_time as u64)
                 .await;
             drop(state_guard);
             // inner loop actually polls
             loop {
+                if idle_if_paused(&paused).await {
+                    continue;
+                }
                 if let Err(err) = rpc.ping() {
                     error!("error ping electrum client in height polling: {:?}", err);
                     // break this loop and retry, since the electrum rpc client is probably
@@ -752,7 +776,11 @@ fn height_polling(
                 let mut block_change = false;
                 for block_notif in blocks.drain(..) {
                     block_change = state_guard
-                        .change_height(block_notif.height, block_notif.block_hash.to_vec())
+                        .change_height(
+                            block_notif.height,
+                            block_notif.block_hash.to_vec(),
+                            block_notif.time as u64,
+                        )
                         .await;
                 }
                 drop(state_guard);
@@ -774,6 +802,7 @@ fn unseen_transaction_polling(
     state: Arc<Mutex<SyncerState>>,
     electrum_server: String,
     proxy_address: Option<String>,
+    paused: Arc<AtomicBool>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn(async move {
         // outer loop ensures the polling restarts if there is an error
@@ -791,6 +820,9 @@ fn unseen_transaction_polling(
                 }
             };
             loop {
+                if idle_if_paused(&paused).await {
+                    continue;
+                }
                 rpc.query_transactions(Arc::clone(&state), true).await;
                 tokio::time::sleep(std::time::Duration::from_secs(10)).await;
             }
@@ -898,6 +930,7 @@ fn estimate_fee_polling(
     electrum_server: String,
     proxy_address: Option<String>,
     state: Arc<Mutex<SyncerState>>,
+    paused: Arc<AtomicBool>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn(async move {
         let high_priority_target = 2;
@@ -906,6 +939,9 @@ fn estimate_fee_polling(
             debug!("creating fee polling electrum client");
             if let Ok(client) = create_electrum_client(&electrum_server, proxy_address.clone()) {
                 loop {
+                    if idle_if_paused(&paused).await {
+                        continue;
+                    }
                     match client.estimate_priority_fee(high_priority_target, low_priority_target) {
                         Ok(FeeByPriority { low_fee, high_fee }) => {
                             let mut state_guard = state.lock().await;
@@ -935,9 +971,13 @@ fn sweep_polling(
     electrum_server: String,
     proxy_address: Option<String>,
     network: bitcoin::Network,
+    paused: Arc<AtomicBool>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn(async move {
         loop {
+            if idle_if_paused(&paused).await {
+                continue;
+            }
             let state_guard = state.lock().await;
             let sweep_addresses = state_guard.sweep_addresses.clone();
             drop(state_guard);
@@ -1055,6 +1095,7 @@ impl Synclet for BitcoinSyncer {
         syncer_address: Vec<u8>,
         opts: &Opts,
         network: Network,
+        paused: Arc<AtomicBool>,
     ) -> Result<(), Error> {
         let btc_network = network.into();
         let proxy_address = opts.shared.tor_proxy.map(|address| address.to_string());
@@ -1094,6 +1135,7 @@ impl Synclet for BitcoinSyncer {
                     run_syncerd_task_receiver(
                         receive_task_channel,
                         Arc::clone(&state),
+                        Arc::clone(&paused),
                         transaction_broadcast_tx,
                         transaction_get_tx,
                         terminate_tx,
@@ -1105,18 +1147,21 @@ impl Synclet for BitcoinSyncer {
                         Arc::clone(&state),
                         electrum_server.clone(),
                         proxy_address.clone(),
+                        Arc::clone(&paused),
                     );
 
                     let height_handle = height_polling(
                         Arc::clone(&state),
                         electrum_server.clone(),
                         proxy_address.clone(),
+                        Arc::clone(&paused),
                     );
 
                     let unseen_transaction_handle = unseen_transaction_polling(
                         Arc::clone(&state),
                         electrum_server.clone(),
                         proxy_address.clone(),
+                        Arc::clone(&paused),
                     );
 
                     let transaction_broadcast_handle = transaction_broadcasting(
@@ -1137,6 +1182,7 @@ impl Synclet for BitcoinSyncer {
                         electrum_server.clone(),
                         proxy_address.clone(),
                         Arc::clone(&state),
+                        Arc::clone(&paused),
                     );
 
                     let sweep_handle = sweep_polling(
@@ -1144,6 +1190,7 @@ impl Synclet for BitcoinSyncer {
                         electrum_server.clone(),
                         proxy_address.clone(),
                         btc_network,
+                        paused,
                     );
 
                     let terminate_handle = terminate_polling(terminate_rx);
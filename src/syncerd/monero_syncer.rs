@@ -3,6 +3,7 @@ use crate::rpc::request::SyncerdBridgeEvent;
 use crate::rpc::Request;
 use crate::service::LogStyle;
 use crate::syncerd::opts::Opts;
+use crate::syncerd::runtime::idle_if_paused;
 use crate::syncerd::runtime::SyncerdTask;
 use crate::syncerd::runtime::Synclet;
 use crate::syncerd::syncer_state::create_set;
@@ -27,6 +28,7 @@ use monero_rpc::{
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
 use std::sync::mpsc::{Receiver, TryRecvError};
 use std::sync::Arc;
 use tokio::sync::mpsc::Receiver as TokioReceiver;
@@ -47,6 +49,7 @@ pub struct MoneroRpc {
 pub struct Block {
     height: u64,
     block_hash: Vec<u8>,
+    time: u64,
 }
 
 #[derive(Debug)]
@@ -127,10 +130,16 @@ impl MoneroRpc {
         let height = self.get_height().await?;
 
         if height != self.height {
-            let block_hash = self.get_block_hash(height).await?;
+            let selector = GetBlockHeaderSelector::Height(height);
+            let header = self.daemon_json_rpc.get_block_header(selector).await?;
+            let block_hash = header.hash.0.to_vec();
             self.height = height;
             self.block_hash = block_hash.clone();
-            Ok(Block { height, block_hash })
+            Ok(Block {
+                height,
+                block_hash,
+                time: header.timestamp.timestamp() as u64,
+            })
         } else {
             Err(Error::Syncer(SyncerError::NoIncrementToHeight))
         }
@@ -380,10 +389,15 @@ impl MoneroSyncer {
 async fn run_syncerd_task_receiver(
     receive_task_channel: Receiver<SyncerdTask>,
     state: Arc<Mutex<SyncerState>>,
+    paused: Arc<AtomicBool>,
     tx_event: TokioSender<SyncerdBridgeEvent>,
 ) {
     tokio::spawn(async move {
         loop {
+            // tasks are left in the channel while paused
+            if idle_if_paused(&paused).await {
+                continue;
+            }
             // this is a hack around the Receiver not being Sync
             let syncerd_task = receive_task_channel.try_recv();
             match syncerd_task {
@@ -452,6 +466,10 @@ async fn run_syncerd_task_receiver(
                             let mut state_guard = state.lock().await;
                             state_guard.watch_transaction(task, syncerd_task.source);
                         }
+                        Task::GetSweepStatus(task) => {
+                            let mut state_guard = state.lock().await;
+                            state_guard.sweep_status(task, syncerd_task.source).await;
+                        }
                         Task::Terminate => {
                             debug!("unimplemented");
                         }
@@ -502,10 +520,14 @@ fn address_polling(
     syncer_servers: MoneroSyncerServers,
     network: monero::Network,
     wallet_mutex: Arc<Mutex<monero_rpc::WalletClient>>,
+    paused: Arc<AtomicBool>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn(async move {
         let mut rpc = MoneroRpc::new(syncer_servers.monero_daemon);
         loop {
+            if idle_if_paused(&paused).await {
+                continue;
+            }
             let state_guard = state.lock().await;
             let mut addresses = state_guard.addresses.clone();
             let subscribed_addresses = state_guard.subscribed_addresses.clone();
@@ -597,10 +619,14 @@ fn address_polling(
 fn height_polling(
     state: Arc<Mutex<SyncerState>>,
     syncer_servers: MoneroSyncerServers,
+    paused: Arc<AtomicBool>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn(async move {
         let mut rpc = MoneroRpc::new(syncer_servers.monero_daemon);
         loop {
+            if idle_if_paused(&paused).await {
+                continue;
+            }
             let block_notif = match rpc.check_block().await {
                 Ok(notif) => Some(notif),
                 Err(Error::Syncer(SyncerError::NoIncrementToHeight)) => None,
@@ -612,7 +638,7 @@ fn height_polling(
             if let Some(block_notif) = block_notif {
                 let mut state_guard = state.lock().await;
                 state_guard
-                    .change_height(block_notif.height, block_notif.block_hash)
+                    .change_height(block_notif.height, block_notif.block_hash, block_notif.time)
                     .await;
                 let mut transactions = state_guard.transactions.clone();
                 drop(state_guard);
@@ -647,9 +673,13 @@ fn sweep_polling(
     wallet: Arc<Mutex<monero_rpc::WalletClient>>,
     network: monero::Network,
     wallet_dir_path: Option<PathBuf>,
+    paused: Arc<AtomicBool>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn(async move {
         loop {
+            if idle_if_paused(&paused).await {
+                continue;
+            }
             let state_guard = state.lock().await;
             let sweep_addresses = state_guard.sweep_addresses.clone();
             drop(state_guard);
@@ -688,10 +718,14 @@ fn sweep_polling(
 fn unseen_transaction_polling(
     state: Arc<Mutex<SyncerState>>,
     syncer_servers: MoneroSyncerServers,
+    paused: Arc<AtomicBool>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn(async move {
         let mut rpc = MoneroRpc::new(syncer_servers.monero_daemon);
         loop {
+            if idle_if_paused(&paused).await {
+                continue;
+            }
             let state_guard = state.lock().await;
             let unseen_transactions = state_guard.unseen_transactions.clone();
             if !unseen_transactions.is_empty() {
@@ -772,6 +806,7 @@ impl Synclet for MoneroSyncer {
         syncer_address: Vec<u8>,
         opts: &Opts,
         network: Network,
+        paused: Arc<AtomicBool>,
     ) -> Result<(), Error> {
         let network = network.into();
         if let Some(daemon) = &opts.monero_daemon {
@@ -808,6 +843,7 @@ impl Synclet for MoneroSyncer {
                         run_syncerd_task_receiver(
                             receive_task_channel,
                             Arc::clone(&state),
+                            Arc::clone(&paused),
                             event_tx.clone(),
                         )
                         .await;
@@ -818,20 +854,28 @@ impl Synclet for MoneroSyncer {
                             syncer_servers.clone(),
                             network,
                             Arc::clone(&wallet_mutex),
+                            Arc::clone(&paused),
                         );
 
                         // transaction polling is done in the same loop
-                        let height_handle =
-                            height_polling(Arc::clone(&state), syncer_servers.clone());
+                        let height_handle = height_polling(
+                            Arc::clone(&state),
+                            syncer_servers.clone(),
+                            Arc::clone(&paused),
+                        );
 
-                        let unseen_transaction_handle =
-                            unseen_transaction_polling(Arc::clone(&state), syncer_servers.clone());
+                        let unseen_transaction_handle = unseen_transaction_polling(
+                            Arc::clone(&state),
+                            syncer_servers.clone(),
+                            Arc::clone(&paused),
+                        );
 
                         let sweep_handle = sweep_polling(
                             Arc::clone(&state),
                             Arc::clone(&wallet_mutex),
                             network,
                             wallet_dir,
+                            paused,
                         );
 
                         let res = tokio::try_join!(
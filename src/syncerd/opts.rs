@@ -61,6 +61,16 @@ pub struct Opts {
     /// Wallet directory use by the monero-wallet-rpc
     #[clap(long)]
     pub monero_wallet_dir_path: Option<String>,
+
+    /// Identifier of a syncer launched with servers overriding the configured ones, the syncer
+    /// then registers as a custom syncer instead of the syncer of the network
+    #[clap(long)]
+    pub custom_syncer_id: Option<u64>,
+
+    /// Name of the configured syncer instance the syncer runs as, the syncer then registers as
+    /// this instance instead of the syncer of the network
+    #[clap(long, conflicts_with = "custom_syncer_id")]
+    pub syncer_instance: Option<String>,
 }
 
 impl Opts {
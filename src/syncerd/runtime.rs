@@ -19,8 +19,10 @@ use crate::syncerd::opts::Opts;
 use crate::syncerd::runtime::request::Progress;
 use farcaster_core::blockchain::{Blockchain, Network};
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Receiver;
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 use internet2::TypedEnum;
@@ -42,9 +44,21 @@ pub trait Synclet {
         syncer_address: Vec<u8>,
         opts: &Opts,
         network: Network,
+        paused: Arc<AtomicBool>,
     ) -> Result<(), Error>;
 }
 
+/// Idles for a second if the syncer is paused for maintenance and returns true, the polling
+/// loops then skip their iteration without touching the chain backend
+pub async fn idle_if_paused(paused: &AtomicBool) -> bool {
+    if paused.load(Ordering::SeqCst) {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        true
+    } else {
+        false
+    }
+}
+
 pub struct SyncerdTask {
     pub task: Task,
     pub source: ServiceId,
@@ -68,15 +82,26 @@ pub fn run(config: ServiceConfig, opts: Opts) -> Result<(), Error> {
     };
 
     let mut runtime = Runtime {
-        identity: ServiceId::Syncer(blockchain, network),
+        identity: ServiceId::syncer(
+            blockchain,
+            network,
+            opts.custom_syncer_id,
+            opts.syncer_instance.clone(),
+        ),
         started: SystemTime::now(),
         tasks: none!(),
         syncer,
         tx,
+        paused: Arc::new(AtomicBool::new(false)),
     };
-    runtime
-        .syncer
-        .run(rx, tx_event, runtime.identity().into(), &opts, network)?;
+    runtime.syncer.run(
+        rx,
+        tx_event,
+        runtime.identity().into(),
+        &opts,
+        network,
+        Arc::clone(&runtime.paused),
+    )?;
     let mut service = Service::service(config, runtime)?;
     service.add_bridge_service_bus(rx_event)?;
     service.run_loop()?;
@@ -89,6 +114,7 @@ pub struct Runtime {
     started: SystemTime,
     tasks: HashSet<u64>, // FIXME
     tx: Sender<SyncerdTask>,
+    paused: Arc<AtomicBool>, // Whether polling and task processing are suspended, e.g. while the chain backend restarts
 }
 
 impl esb::Handler<ServiceBus> for Runtime {
@@ -180,6 +206,7 @@ impl Runtime {
                             .unwrap_or_else(|_| Duration::from_secs(0))
                             .as_secs(),
                         tasks: self.tasks.iter().cloned().collect(),
+                        paused: self.paused.load(Ordering::SeqCst),
                     }),
                 )?;
             }
@@ -195,6 +222,20 @@ impl Runtime {
                 notify_cli = Some((Some(source), resp));
             }
 
+            (Request::PauseSyncer(..), ServiceId::Farcasterd) => {
+                info!(
+                    "{} is {} for maintenance, tasks are queued until it resumes",
+                    self.identity(),
+                    "paused".bright_yellow_bold()
+                );
+                self.paused.store(true, Ordering::SeqCst);
+            }
+
+            (Request::ResumeSyncer(..), ServiceId::Farcasterd) => {
+                info!("{} is {}", self.identity(), "resumed".bright_green_bold());
+                self.paused.store(false, Ordering::SeqCst);
+            }
+
             (Request::Terminate, ServiceId::Farcasterd) => {
                 // terminate all runtimes
                 info!("Received terminate on {}", self.identity());
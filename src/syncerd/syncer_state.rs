@@ -3,13 +3,22 @@ use crate::syncerd::{TaskId, TaskTarget};
 use crate::Error;
 use crate::ServiceId;
 use farcaster_core::blockchain::Blockchain;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::Sender as TokioSender;
 
 use crate::service::LogStyle;
 use crate::syncerd::*;
 use hex;
 
+/// How long the latest fee estimation is handed out to new fee estimation tasks without waiting
+/// for the next poll, spans a few polling rounds so a single failed poll doesn't evict it
+pub const FEE_ESTIMATION_TTL: Duration = Duration::from_secs(60);
+
+/// How many completed sweeps are remembered to answer sweep status queries whose success event
+/// may have been lost
+pub const COMPLETED_SWEEPS_KEPT: usize = 100;
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Hash, Display)]
 #[display(Debug)]
 pub struct InternalId(u32);
@@ -34,6 +43,7 @@ pub struct SyncerState {
     blockchain: Blockchain,
     block_height: u64,
     block_hash: Vec<u8>,
+    block_time: u64,
     tasks_sources: HashMap<InternalId, ServiceId>,
     watch_height: HashMap<InternalId, WatchHeight>,
     watch_fee_estimation: HashMap<InternalId, WatchEstimateFee>,
@@ -42,10 +52,13 @@ pub struct SyncerState {
     pub transactions: HashMap<InternalId, WatchedTransaction>,
     pub unseen_transactions: HashSet<InternalId>,
     pub sweep_addresses: HashMap<InternalId, SweepAddress>,
+    completed_sweeps: VecDeque<(ServiceId, TaskId, Vec<Vec<u8>>)>,
     tx_event: TokioSender<SyncerdBridgeEvent>,
     task_count: TaskCounter,
     pub subscribed_addresses: HashSet<AddressAddendum>,
     pub fee_estimation: Option<FeeEstimations>,
+    fee_estimation_at: Option<Instant>,
+    unanswered_fee_estimation: HashSet<InternalId>,
 }
 
 #[derive(Clone, Debug)]
@@ -77,6 +90,7 @@ impl SyncerState {
         Self {
             block_height: 0,
             block_hash: vec![0],
+            block_time: 0,
             tasks_sources: HashMap::new(),
             watch_height: HashMap::new(),
             watch_fee_estimation: HashMap::new(),
@@ -85,11 +99,14 @@ impl SyncerState {
             transactions: HashMap::new(),
             unseen_transactions: HashSet::new(),
             sweep_addresses: HashMap::new(),
+            completed_sweeps: VecDeque::new(),
             tx_event,
             task_count: TaskCounter(0),
             blockchain,
             subscribed_addresses: HashSet::new(),
             fee_estimation: None,
+            fee_estimation_at: None,
+            unanswered_fee_estimation: HashSet::new(),
         }
     }
 
@@ -273,6 +290,7 @@ impl SyncerState {
                         id: task.id,
                         block: self.block_hash.clone(),
                         height: self.block_height,
+                        time: self.block_time,
                     }),
                     source,
                 )],
@@ -343,14 +361,20 @@ impl SyncerState {
         self.tasks_sources
             .insert(self.task_count.into(), source.clone());
 
-        // try to emit an event immediately from the cached values
-        if let Some(ref fee_estimations) = &self.fee_estimation {
+        // try to emit an event immediately from the cached values, shared by all the swaps using
+        // this syncer, a stale estimation is only sent once polling refreshes it
+        let fee_estimations = self.fresh_fee_estimation().cloned();
+        if fee_estimations.is_none() {
+            self.unanswered_fee_estimation
+                .insert(self.task_count.into());
+        }
+        if let Some(fee_estimations) = fee_estimations {
             send_event(
                 &self.tx_event,
                 &mut vec![(
                     Event::FeeEstimation(FeeEstimation {
                         id: task.id,
-                        fee_estimations: fee_estimations.clone(),
+                        fee_estimations,
                     }),
                     source,
                 )],
@@ -371,9 +395,10 @@ impl SyncerState {
         self.sweep_addresses.insert(self.task_count.into(), task);
         self.tasks_sources.insert(self.task_count.into(), source);
     }
-    pub async fn change_height(&mut self, new_height: u64, block: Vec<u8>) -> bool {
+    pub async fn change_height(&mut self, new_height: u64, block: Vec<u8>, time: u64) -> bool {
         if self.block_height != new_height || self.block_hash != block {
             self.handle_change_height(new_height, block.clone());
+            self.block_time = time;
             self.drop_lifetimes();
 
             // Emit a height_changed event
@@ -385,6 +410,7 @@ impl SyncerState {
                             id: task.id,
                             block: block.clone(),
                             height: self.block_height,
+                            time: self.block_time,
                         }),
                         self.tasks_sources.get(id).unwrap().clone(),
                     )],
@@ -559,6 +585,18 @@ impl SyncerState {
                     } else {
                         unseen_transactions.insert(*id);
                     }
+                    // a transaction losing confirmations was reorged out of its block
+                    if let Some(previous) = watched_tx.transaction_confirmations.confirmations {
+                        if previous > 0 && confirmations.unwrap_or(0) < previous {
+                            events.push((
+                                Event::Reorg(Reorg {
+                                    id: watched_tx.task.id,
+                                    hash: tx_id.clone(),
+                                }),
+                                tasks_sources.get(id).unwrap().clone(),
+                            ));
+                        }
+                    }
                     let transaction_confirmations = if confirmations
                         != watched_tx.transaction_confirmations.confirmations
                         || block != watched_tx.transaction_confirmations.block
@@ -603,7 +641,7 @@ impl SyncerState {
                 &mut vec![(
                     Event::SweepSuccess(SweepSuccess {
                         id: sweep_address.id,
-                        txids,
+                        txids: txids.clone(),
                     }),
                     self.tasks_sources
                         .get(id)
@@ -618,15 +656,66 @@ impl SyncerState {
                     self.lifetimes.remove(&sweep_address.lifetime);
                 }
             }
-            self.sweep_addresses.remove(id);
-            self.tasks_sources.remove(id);
+            if let (Some(sweep_address), Some(source)) = (
+                self.sweep_addresses.remove(id),
+                self.tasks_sources.remove(id),
+            ) {
+                if self.completed_sweeps.len() == COMPLETED_SWEEPS_KEPT {
+                    self.completed_sweeps.pop_front();
+                }
+                self.completed_sweeps
+                    .push_back((source, sweep_address.id, txids));
+            }
+        }
+    }
+
+    pub async fn sweep_status(&mut self, task: GetSweepStatus, source: ServiceId) {
+        let pending = self
+            .sweep_addresses
+            .iter()
+            .any(|(internal_id, sweep_address)| {
+                sweep_address.id == task.id && self.tasks_sources.get(internal_id) == Some(&source)
+            });
+        let status = if pending {
+            SweepTaskStatus::Pending
+        } else if let Some((_, _, txids)) = self
+            .completed_sweeps
+            .iter()
+            .rev()
+            .find(|(sweep_source, id, _)| *sweep_source == source && *id == task.id)
+        {
+            SweepTaskStatus::Completed(txids.clone())
+        } else {
+            SweepTaskStatus::Unknown
+        };
+        send_event(
+            &self.tx_event,
+            &mut vec![(
+                Event::SweepStatus(SweepStatus {
+                    id: task.id,
+                    status,
+                }),
+                source,
+            )],
+        )
+        .await;
+    }
+
+    /// The latest fee estimation if it was polled less than `FEE_ESTIMATION_TTL` ago
+    pub fn fresh_fee_estimation(&self) -> Option<&FeeEstimations> {
+        match self.fee_estimation_at {
+            Some(at) if at.elapsed() < FEE_ESTIMATION_TTL => self.fee_estimation.as_ref(),
+            _ => None,
         }
     }
 
     pub async fn fee_estimated(&mut self, fee_estimations: FeeEstimations) {
-        // Emit fee estimation events
-        if self.fee_estimation.as_ref() != Some(&fee_estimations) {
-            for (id, task) in self.watch_fee_estimation.iter() {
+        // Emit fee estimation events to every task if the estimation changed, otherwise only to
+        // the tasks registered while the estimation was stale, which did not receive it yet
+        let changed = self.fee_estimation.as_ref() != Some(&fee_estimations);
+        let unanswered = std::mem::take(&mut self.unanswered_fee_estimation);
+        for (id, task) in self.watch_fee_estimation.iter() {
+            if changed || unanswered.contains(id) {
                 send_event(
                     &self.tx_event,
                     &mut vec![(
@@ -639,9 +728,9 @@ impl SyncerState {
                 )
                 .await;
             }
-
-            self.fee_estimation = Some(fee_estimations);
         }
+        self.fee_estimation = Some(fee_estimations);
+        self.fee_estimation_at = Some(Instant::now());
         self.drop_lifetimes();
     }
 
@@ -708,6 +797,7 @@ impl SyncerState {
                 self.unseen_transactions.remove(task);
                 self.watch_height.remove(task);
                 self.watch_fee_estimation.remove(task);
+                self.unanswered_fee_estimation.remove(task);
                 self.sweep_addresses.remove(task);
                 self.tasks_sources.remove(task);
             }
@@ -878,6 +968,14 @@ async fn syncer_state_transaction() {
     assert_eq!(state.transactions.len(), 2);
     assert_eq!(state.tasks_sources.len(), 3);
     assert_eq!(state.unseen_transactions.len(), 2);
+    // losing its confirmation, the transaction is reported as reorged
+    assert!(matches!(
+        event_rx.try_recv(),
+        Ok(SyncerdBridgeEvent {
+            event: Event::Reorg(..),
+            ..
+        })
+    ));
     assert!(event_rx.try_recv().is_ok());
 
     let source2 = ServiceId::Syncer(Blockchain::Monero, Network::Mainnet);
@@ -891,7 +989,7 @@ async fn syncer_state_transaction() {
     assert_eq!(state.unseen_transactions.len(), 2);
     assert!(event_rx.try_recv().is_ok());
 
-    state.change_height(5, vec![1]).await;
+    state.change_height(5, vec![1], 0).await;
     assert_eq!(state.lifetimes.len(), 0);
     assert_eq!(state.transactions.len(), 0);
     assert_eq!(state.tasks_sources.len(), 0);
@@ -1034,7 +1132,7 @@ async fn syncer_state_addresses() {
     assert_eq!(state.addresses.len(), 1);
     assert!(event_rx.try_recv().is_ok());
 
-    state.change_height(1, vec![1]).await;
+    state.change_height(1, vec![1], 0).await;
     let height_task = WatchHeight {
         id: TaskId(0),
         lifetime: 3,
@@ -1044,7 +1142,7 @@ async fn syncer_state_addresses() {
     assert_eq!(state.tasks_sources.len(), 2);
     assert!(event_rx.try_recv().is_ok());
 
-    state.change_height(2, vec![2]).await;
+    state.change_height(2, vec![2], 0).await;
     state
         .change_address(
             addendum.clone(),
@@ -1107,11 +1205,46 @@ async fn syncer_state_sweep_addresses() {
     assert_eq!(state.lifetimes.len(), 1);
     assert_eq!(state.tasks_sources.len(), 1);
     assert_eq!(state.sweep_addresses.len(), 1);
+    state
+        .sweep_status(GetSweepStatus { id: TaskId(0) }, source1.clone())
+        .await;
+    let event = event_rx.try_recv().unwrap().event;
+    assert_eq!(
+        event,
+        Event::SweepStatus(SweepStatus {
+            id: TaskId(0),
+            status: SweepTaskStatus::Pending
+        })
+    );
     state.success_sweep(&InternalId(2), vec![vec![0]]).await;
     assert_eq!(state.lifetimes.len(), 0);
     assert_eq!(state.tasks_sources.len(), 0);
     assert_eq!(state.sweep_addresses.len(), 0);
     assert!(event_rx.try_recv().is_ok());
+
+    // the completed sweep is still reported if its success event got lost
+    state
+        .sweep_status(GetSweepStatus { id: TaskId(0) }, source1.clone())
+        .await;
+    let event = event_rx.try_recv().unwrap().event;
+    assert_eq!(
+        event,
+        Event::SweepStatus(SweepStatus {
+            id: TaskId(0),
+            status: SweepTaskStatus::Completed(vec![vec![0]])
+        })
+    );
+    state
+        .sweep_status(GetSweepStatus { id: TaskId(1) }, source1)
+        .await;
+    let event = event_rx.try_recv().unwrap().event;
+    assert_eq!(
+        event,
+        Event::SweepStatus(SweepStatus {
+            id: TaskId(1),
+            status: SweepTaskStatus::Unknown
+        })
+    );
 }
 
 #[tokio::test]
@@ -1156,19 +1289,19 @@ async fn syncer_state_height() {
     assert_eq!(state.watch_height.len(), 2);
     assert!(event_rx.try_recv().is_err());
 
-    state.change_height(1, vec![1]).await;
+    state.change_height(1, vec![1], 0).await;
     assert_eq!(state.lifetimes.len(), 1);
     assert_eq!(state.tasks_sources.len(), 1);
     assert_eq!(state.watch_height.len(), 1);
     assert!(event_rx.try_recv().is_ok());
 
-    state.change_height(3, vec![3]).await;
+    state.change_height(3, vec![3], 0).await;
     assert_eq!(state.lifetimes.len(), 1);
     assert_eq!(state.tasks_sources.len(), 1);
     assert_eq!(state.watch_height.len(), 1);
     assert!(event_rx.try_recv().is_ok());
 
-    state.change_height(3, vec![3]).await;
+    state.change_height(3, vec![3], 0).await;
     assert_eq!(state.lifetimes.len(), 1);
     assert_eq!(state.tasks_sources.len(), 1);
     assert_eq!(state.watch_height.len(), 1);
@@ -1187,9 +1320,60 @@ async fn syncer_state_height() {
     assert!(event_rx.try_recv().is_ok());
     assert!(event_rx.try_recv().is_ok());
 
-    state.change_height(4, vec![0]).await;
+    state.change_height(4, vec![0], 0).await;
     assert_eq!(state.lifetimes.len(), 0);
     assert_eq!(state.tasks_sources.len(), 0);
     assert_eq!(state.watch_height.len(), 0);
     assert!(event_rx.try_recv().is_err());
 }
+
+#[tokio::test]
+async fn syncer_state_fee_estimation() {
+    use farcaster_core::blockchain::Network;
+    use tokio::sync::mpsc::Receiver as TokioReceiver;
+
+    let (event_tx, mut event_rx): (
+        TokioSender<SyncerdBridgeEvent>,
+        TokioReceiver<SyncerdBridgeEvent>,
+    ) = tokio::sync::mpsc::channel(120);
+    let mut state = SyncerState::new(event_tx.clone(), Blockchain::Bitcoin);
+    let fee_task = WatchEstimateFee {
+        id: TaskId(0),
+        lifetime: 0,
+    };
+    let source1 = ServiceId::Syncer(Blockchain::Bitcoin, Network::Mainnet);
+    let source2 = ServiceId::Syncer(Blockchain::Bitcoin, Network::Testnet);
+
+    // nothing cached yet, the task waits for the first poll
+    state.estimate_fee(fee_task.clone(), source1.clone()).await;
+    assert!(event_rx.try_recv().is_err());
+
+    let fee_estimations = FeeEstimations::BitcoinFeeEstimation {
+        high_priority_sats_per_kvbyte: 2000,
+        low_priority_sats_per_kvbyte: 1000,
+    };
+    state.fee_estimated(fee_estimations.clone()).await;
+    assert!(event_rx.try_recv().is_ok());
+
+    // a fresh estimation is shared with new tasks immediately
+    state.estimate_fee(fee_task.clone(), source2.clone()).await;
+    assert!(event_rx.try_recv().is_ok());
+    assert_eq!(state.fresh_fee_estimation(), Some(&fee_estimations));
+
+    // a stale estimation is not handed out
+    state.fee_estimation_at = Instant::now().checked_sub(FEE_ESTIMATION_TTL * 2);
+    assert_eq!(state.fresh_fee_estimation(), None);
+    state.estimate_fee(fee_task, source2).await;
+    assert!(event_rx.try_recv().is_err());
+
+    // an unchanged poll result refreshes the estimation and only notifies the task registered
+    // while it was stale
+    state.fee_estimated(fee_estimations.clone()).await;
+    assert!(event_rx.try_recv().is_ok());
+    assert!(event_rx.try_recv().is_err());
+    assert_eq!(state.fresh_fee_estimation(), Some(&fee_estimations));
+
+    // the tasks that received the estimation are not notified again while it does not change
+    state.fee_estimated(fee_estimations.clone()).await;
+    assert!(event_rx.try_recv().is_err());
+}
@@ -140,6 +140,13 @@ pub struct WatchEstimateFee {
     pub lifetime: u64,
 }
 
+/// Asks the syncer where a sweep task stands, answered with a [`SweepStatus`] event
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, Eq, PartialEq, Hash)]
+#[display(Debug)]
+pub struct GetSweepStatus {
+    pub id: TaskId,
+}
+
 /// Tasks created by the daemon and handle by syncers to process a blockchain
 /// and generate [`Event`] back to the syncer.
 #[derive(Clone, Debug, Display, StrictEncode, StrictDecode, Eq, PartialEq, Hash)]
@@ -153,6 +160,7 @@ pub enum Task {
     SweepAddress(SweepAddress),
     GetTx(GetTx),
     WatchEstimateFee(WatchEstimateFee),
+    GetSweepStatus(GetSweepStatus),
     Terminate,
 }
 
@@ -169,6 +177,8 @@ pub struct HeightChanged {
     pub id: TaskId,
     pub block: Vec<u8>,
     pub height: u64,
+    /// Timestamp of the block, in seconds since the unix epoch
+    pub time: u64,
 }
 
 #[derive(Clone, Debug, Display, StrictEncode, StrictDecode, Eq, PartialEq, Hash)]
@@ -200,6 +210,13 @@ pub struct TransactionBroadcasted {
     pub error: Option<String>,
 }
 
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, Eq, PartialEq, Hash)]
+#[display(Debug)]
+pub struct Reorg {
+    pub id: TaskId,
+    pub hash: Vec<u8>,
+}
+
 #[derive(Clone, Debug, Display, StrictEncode, StrictDecode, Eq, PartialEq, Hash)]
 #[display(Debug)]
 pub struct SweepSuccess {
@@ -207,6 +224,24 @@ pub struct SweepSuccess {
     pub txids: Vec<Vec<u8>>,
 }
 
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, Eq, PartialEq, Hash)]
+#[display(Debug)]
+pub enum SweepTaskStatus {
+    /// The sweep task is still watched by the syncer
+    Pending,
+    /// The sweep completed, carries the txids of the sweep transactions
+    Completed(Vec<Vec<u8>>),
+    /// The syncer does not know the task, e.g. it restarted since the task was sent
+    Unknown,
+}
+
+#[derive(Clone, Debug, Display, StrictEncode, StrictDecode, Eq, PartialEq, Hash)]
+#[display(Debug)]
+pub struct SweepStatus {
+    pub id: TaskId,
+    pub status: SweepTaskStatus,
+}
+
 #[derive(Clone, Debug, Display, StrictEncode, StrictDecode, Eq, PartialEq, Hash)]
 #[display(Debug)]
 pub struct TransactionRetrieved {
@@ -243,7 +278,12 @@ pub enum Event {
     AddressTransaction(AddressTransaction),
     TransactionConfirmations(TransactionConfirmations),
     TransactionBroadcasted(TransactionBroadcasted),
+    /// Notify the daemon a watched transaction lost confirmations, i.e. it was
+    /// reorged out of its block. Confirmations are then reported again.
+    Reorg(Reorg),
     SweepSuccess(SweepSuccess),
+    /// Answers a [`GetSweepStatus`] task.
+    SweepStatus(SweepStatus),
     /// Notify the daemon the task has been aborted with success or failure.
     /// Carries the status for the task abortion.
     TaskAborted(TaskAborted),
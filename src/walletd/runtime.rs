@@ -1272,6 +1272,7 @@ impl Runtime {
                 public_offer,
                 external_address,
                 internal_address,
+                ..
             }) if source == ServiceId::Farcasterd => {
                 let PublicOffer { offer, .. } = public_offer.clone();
 
@@ -1,3 +1,6 @@
+use std::convert::TryFrom;
+use std::net::SocketAddr;
+use std::str::FromStr;
 use std::time;
 use utils::fc::*;
 
@@ -51,3 +54,98 @@ async fn cli_make_offer() {
     // clean up processes
     cleanup_processes(vec![farcasterd_maker, farcasterd_taker]);
 }
+
+#[tokio::test]
+#[ignore]
+async fn cli_make_offers_on_same_port() {
+    let (farcasterd_maker, data_dir_maker, farcasterd_taker, _) = setup_clients().await;
+
+    // Allow some time for the microservices to start and register each other
+    tokio::time::sleep(time::Duration::from_secs(10)).await;
+
+    let make_args = |btc_amount: &str, xmr_amount: &str, bind_ip: &str| {
+        let mut args: Vec<String> = vec![
+            "make",
+            "--btc-addr",
+            "tb1q4gj53tuew3e6u4a32kdtle2q72su8te39dpceq",
+            "--xmr-addr",
+            "55LTR8KniP4LQGJSPtbYDacR7dz8RBFnsfAKMaMuwUNYX6aQbBcovzDPyrQF9KXF9tVU6Xk3K8no1BywnJX6GvZX8yJsXvt",
+            "--btc-amount",
+            btc_amount,
+            "--xmr-amount",
+            xmr_amount,
+            "--network",
+            "Testnet",
+            "--bind-ip-addr",
+            bind_ip,
+            "-p",
+            "9377",
+        ]
+        .into_iter()
+        .map(|s| s.to_string())
+        .collect();
+        args.append(&mut data_dir_maker.clone());
+        args
+    };
+
+    // two offers with different parameters on the same bind address share the listener
+    let first: MadeOffer = cli(make_args("0.01 BTC", "1 XMR", "0.0.0.0")).unwrap();
+    let second: MadeOffer = cli(make_args("0.02 BTC", "2 XMR", "0.0.0.0")).unwrap();
+    assert_ne!(first.offer_info.offer, second.offer_info.offer);
+    assert_eq!(
+        first.offer_info.details.peer_address,
+        second.offer_info.details.peer_address
+    );
+
+    // the same port on another bind address conflicts with the existing listener
+    let conflicting: Result<MadeOffer, String> = cli(make_args("0.03 BTC", "3 XMR", "127.0.0.1"));
+    assert!(conflicting.is_err());
+
+    // clean up processes
+    cleanup_processes(vec![farcasterd_maker, farcasterd_taker]);
+}
+
+#[tokio::test]
+#[ignore]
+async fn cli_make_offer_ipv6() {
+    let (farcasterd_maker, data_dir_maker, farcasterd_taker, _) = setup_clients().await;
+
+    // Allow some time for the microservices to start and register each other
+    tokio::time::sleep(time::Duration::from_secs(10)).await;
+
+    let mut args = vec![
+        "make",
+        "--btc-addr",
+        "tb1q4gj53tuew3e6u4a32kdtle2q72su8te39dpceq",
+        "--xmr-addr",
+        "55LTR8KniP4LQGJSPtbYDacR7dz8RBFnsfAKMaMuwUNYX6aQbBcovzDPyrQF9KXF9tVU6Xk3K8no1BywnJX6GvZX8yJsXvt",
+        "--btc-amount",
+        "0.01 BTC",
+        "--xmr-amount",
+        "1 XMR",
+        "--network",
+        "Testnet",
+        "--public-ip-addr",
+        "::1",
+        "--bind-ip-addr",
+        "::1",
+        "-p",
+        "9378",
+    ];
+    args.append(&mut data_dir_maker.iter().map(std::ops::Deref::deref).collect());
+
+    let res: MadeOffer = cli(args).unwrap();
+    assert_eq!(
+        SocketAddr::try_from(res.offer_info.details.peer_address).unwrap(),
+        SocketAddr::from_str("[::1]:9378").unwrap()
+    );
+
+    // the listener is bound to the IPv6 address, listed with the address bracketed
+    let mut args = vec!["list-listens"];
+    args.append(&mut data_dir_maker.iter().map(std::ops::Deref::deref).collect());
+    let listens: Vec<String> = cli(args).unwrap();
+    assert!(listens.contains(&"[::1]:9378".to_string()));
+
+    // clean up processes
+    cleanup_processes(vec![farcasterd_maker, farcasterd_taker]);
+}